@@ -1,33 +1,100 @@
-pub mod day25;
-
-pub use day25 as day;
-
-pub const DAY: usize = 25;
-
-// Uncomment below to compile all days instead of just the current one.
-
-// pub mod day1;
-// pub mod day2;
-// pub mod day3;
-// pub mod day4;
-// pub mod day5;
-// pub mod day6;
-// pub mod day7;
-// pub mod day8;
-// pub mod day9;
-// pub mod day10;
-// pub mod day11;
-// pub mod day12;
-// pub mod day13;
-// pub mod day14;
-// pub mod day15;
-// pub mod day16;
-// pub mod day17;
-// pub mod day18;
-// pub mod day19;
-// pub mod day20;
-// pub mod day21;
-// pub mod day22;
-// pub mod day23;
-// pub mod day24;
-// pub mod day25;
+use crate::harness::RegisteredDay;
+
+// Shadow the standard `print!` and `println!` in the day modules, so that the
+// harness can show what each part printed alongside its result. They're only
+// used while debugging.
+#[allow(unused_macros)]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::harness::output::print(format_args!($($arg)*))
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! println {
+    () => {
+        print!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::harness::output::print(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Declares each day's module and registers its `Day` in `DAYS`.
+macro_rules! days {
+    ($($module:ident::$day:ident $(($part1:ident, $part2:ident))?),* $(,)?) => {
+        $(
+            pub mod $module;
+        )*
+
+        /// Every solved day, in order.
+        pub static DAYS: &[RegisteredDay] = &[
+            $(
+                RegisteredDay::new($crate::registered_solver!($module::$day $(($part1, $part2))?)),
+            )*
+        ];
+    };
+}
+
+/// The `Day` registered by an entry in `with_days!`, as a `&'static` reference:
+/// the module's `Day` type, or for an entry like `day1::Day1(solve_part1,
+/// solve_part2)`, the module's part functions adapted by `PartFns`, with `Day1`
+/// implementing `Puzzle`. `$module` must be in scope.
+#[macro_export]
+macro_rules! registered_solver {
+    ($module:ident::$day:ident) => {
+        &$module::$day
+    };
+    ($module:ident::$day:ident($part1:ident, $part2:ident)) => {
+        &$crate::harness::day::PartFns::<$module::$day, _, _>::new($module::$part1, $module::$part2)
+    };
+}
+
+/// Invokes `$callback! { day1::Day1, day2::Day2, ... }` with the module and
+/// `Day` type of every registered day, so that code outside this module can
+/// generate something per day. Days written as a pair of part functions are
+/// listed as `dayN::DayN(solve_part1, solve_part2)`; `registered_solver!`
+/// turns either kind of entry into a `Day`.
+#[macro_export]
+macro_rules! with_days {
+    ($callback:ident) => {
+        $callback! {
+            day1::Day1,
+            day2::Day2,
+            day3::Day3,
+            day4::Day4,
+            day5::Day5,
+            day6::Day6,
+            day7::Day7,
+            day8::Day8,
+            day9::Day9,
+            day10::Day10,
+            day11::Day11,
+            day12::Day12,
+            day13::Day13,
+            day14::Day14,
+            day15::Day15,
+            day16::Day16,
+            day17::Day17,
+            day18::Day18,
+            day19::Day19,
+            day20::Day20,
+            day21::Day21,
+            day22::Day22,
+            day23::Day23,
+            day24::Day24,
+            day25::Day25,
+        }
+    };
+}
+
+with_days!(days);
+
+pub fn get(day: usize) -> Option<&'static RegisteredDay> {
+    DAYS.iter().find(|registered| registered.day == day)
+}
+
+/// The most recent day, which is run when no day is specified.
+pub fn latest() -> &'static RegisteredDay {
+    DAYS.last().unwrap()
+}
@@ -1,16 +1,30 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 
-pub fn solve_part1(input: RawInput) -> u32 {
-    parse_sums(input).max().unwrap()
-}
+pub struct Day1;
+
+impl Day for Day1 {
+    const NUMBER: usize = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    type Output1 = u32;
+    type Output2 = u32;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> u32 {
+        parse_sums(*input).max().unwrap()
+    }
 
-pub fn solve_part2(input: RawInput) -> u32 {
-    let mut sums: Vec<_> = parse_sums(input).collect();
-    sums.sort();
-    sums.into_iter().rev().take(3).sum()
+    fn part2(&self, input: &RawInput) -> u32 {
+        let mut sums: Vec<_> = parse_sums(*input).collect();
+        sums.sort();
+        sums.into_iter().rev().take(3).sum()
+    }
 }
 
-fn parse_sums(input: RawInput) -> impl Iterator<Item = u32> + '_ {
+fn parse_sums(input: RawInput<'_>) -> impl Iterator<Item = u32> + '_ {
     input
         .grouped_lines(|line| line.single::<u32>())
         .map(|group| group.into_iter().sum())
@@ -1,38 +1,58 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
+use crate::util::ocr::{self, OcrError};
+use std::mem;
 use std::num::ParseIntError;
 use std::str::FromStr;
 use Instruction::{Addx, Noop};
 
-pub fn solve_part1(input: RawInput) -> i32 {
-    let instructions = input.per_line(|line| line.single()).collect();
-    let mut state = State::new(instructions);
-    let mut result = 0;
-    while !state.is_done() {
-        let x = state.x;
-        state.advance();
-        if (state.cycle - 20) % 40 == 0 {
-            result += state.cycle * x;
+pub struct Day10;
+
+impl Day for Day10 {
+    const NUMBER: usize = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    type Output1 = i32;
+    type Output2 = Result<String, OcrError>;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> i32 {
+        let instructions = input.per_line(|line| line.single()).collect();
+        let mut state = State::new(instructions);
+        let mut result = 0;
+        while !state.is_done() {
+            let x = state.x;
+            state.advance();
+            if (state.cycle - 20) % 40 == 0 {
+                result += state.cycle * x;
+            }
         }
+        result
+    }
+
+    fn part2(&self, input: &RawInput) -> Result<String, OcrError> {
+        ocr::read_str(&draw(*input))
     }
-    result
 }
 
-pub fn solve_part2(input: RawInput) -> u8 {
+/// Draws the CRT's screen, one row of `#` and `.` pixels per line.
+fn draw(input: RawInput) -> String {
     let instructions = input.per_line(|line| line.single()).collect();
     let mut state = State::new(instructions);
-    let mut chars = vec![];
+    let mut rows = vec![];
+    let mut row = String::new();
     while !state.is_done() {
         let x = state.x;
         let column = state.cycle % 40;
-        let char = if (column - x).abs() <= 1 { '█' } else { ' ' };
-        chars.push(char);
+        row.push(if (column - x).abs() <= 1 { '#' } else { '.' });
         if column == 39 {
-            chars.push('\n');
+            rows.push(mem::take(&mut row));
         }
         state.advance();
     }
-    println!("{}", chars.into_iter().collect::<String>());
-    0
+    rows.join("\n")
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -90,3 +110,26 @@ impl State {
         self.instruction_index == self.instructions.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::test_input::{self, TestInput};
+    use std::path::Path;
+
+    #[test]
+    fn test_draw() {
+        let files = test_input::read_test_files(Path::new("input"), 10).unwrap();
+        let file = files.iter().find(|file| file.name == "input").unwrap();
+        let test_input = TestInput::parse(file).unwrap();
+        assert_eq!(
+            draw(test_input.raw_input()),
+            "##..##..##..##..##..##..##..##..##..##..\n\
+             ###...###...###...###...###...###...###.\n\
+             ####....####....####....####....####....\n\
+             #####.....#####.....#####.....#####.....\n\
+             ######......######......######......####\n\
+             #######.......#######.......#######....."
+        );
+    }
+}
@@ -1,21 +1,33 @@
-use crate::harness::input::RawInput;
-use crate::regex;
-use crate::util::re::{parse_with_regex, Str};
+use crate::harness::day::Day;
+use crate::harness::input::{InputError, LineInput, RawInput};
+use crate::unparsed_input;
 use std::collections::VecDeque;
 use std::error;
 use std::str::FromStr;
 use Op::{Add, Mul, Square};
 
-pub fn solve_part1(input: RawInput) -> u64 {
-    solve(input, 20, 3, false)
-}
+pub struct Day11;
+
+impl Day for Day11 {
+    const NUMBER: usize = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+
+    type Output1 = u64;
+    type Output2 = u64;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> u64 {
-    solve(input, 10000, 1, true)
+    fn part1(&self, input: &RawInput) -> u64 {
+        solve(*input, 20, 3, false)
+    }
+
+    fn part2(&self, input: &RawInput) -> u64 {
+        solve(*input, 10000, 1, true)
+    }
 }
 
 fn solve(input: RawInput, num_rounds: u32, divisor: u64, use_modulus: bool) -> u64 {
-    let mut monkeys: Monkeys = input.as_str().parse().unwrap();
+    let mut monkeys = Monkeys::parse(input).unwrap();
     let modulus = if use_modulus {
         monkeys.0.iter().map(|monkey| monkey.test_factor).product()
     } else {
@@ -30,17 +42,15 @@ fn solve(input: RawInput, num_rounds: u32, divisor: u64, use_modulus: bool) -> u
 #[derive(Debug)]
 struct Monkeys(Vec<Monkey>);
 
-impl FromStr for Monkeys {
-    type Err = Box<dyn error::Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let monkeys: Result<Vec<Monkey>, Self::Err> =
-            s.trim().split("\n\n").map(str::parse).collect();
+impl Monkeys {
+    fn parse(input: RawInput) -> Result<Self, Box<dyn error::Error>> {
+        let monkeys: Result<Vec<Monkey>, _> = input
+            .sections()
+            .map(|section| section.as_str().parse())
+            .collect();
         Ok(Self(monkeys?))
     }
-}
 
-impl Monkeys {
     fn execute_round(&mut self, divisor: u64, modulus: u64) {
         for i in 0..self.0.len() {
             while let Some((worry_level, target)) = self.0[i].toss_next_item(divisor, modulus) {
@@ -71,33 +81,20 @@ impl FromStr for Monkey {
     type Err = Box<dyn error::Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = regex!(
-            r"Monkey \d+:
-  Starting items: (.+?)
-  Operation: new = old (.) (.+?)
-  Test: divisible by (\d+)
-    If true: throw to monkey (\d+)
-    If false: throw to monkey (\d+)"
-        );
-        let (Str(items), op_char, Str(op_value), test_factor, true_target, false_target): (
-            Str,
-            char,
-            Str,
-            u64,
-            usize,
-            usize,
-        ) = parse_with_regex(re, s)?;
-        let items: VecDeque<u64> = items.split(", ").map(|s| s.parse().unwrap()).collect();
-        let op = if op_value == "old" {
-            Square
-        } else {
-            let op_value = op_value.parse().unwrap();
-            if op_char == '+' {
-                Add(op_value)
-            } else {
-                Mul(op_value)
-            }
+        let lines: Vec<_> = s.lines().map(LineInput::new).collect();
+        let [_, items, op, test, if_true, if_false] = lines[..] else {
+            return Err(format!("Expected 6 lines, found {}.", lines.len()).into());
+        };
+        let items = items.try_ints::<u64>()?.into();
+        let op = match op.words()[..] {
+            [.., "*", "old"] => Square,
+            [.., "+", _] => Add(op.try_ints_array::<_, 1>()?[0]),
+            [.., "*", _] => Mul(op.try_ints_array::<_, 1>()?[0]),
+            _ => return Err(InputError::new(&op, "Unknown operation.").into()),
         };
+        let [test_factor] = test.try_ints_array()?;
+        let [true_target] = if_true.try_ints_array()?;
+        let [false_target] = if_false.try_ints_array()?;
         Ok(Self {
             items,
             op,
@@ -113,7 +110,7 @@ impl Monkey {
     fn toss_next_item(&mut self, divisor: u64, modulus: u64) -> Option<(u64, usize)> {
         self.items.pop_front().map(|item| {
             let worry_level = (self.op.apply(item) / divisor) % modulus;
-            let target = if worry_level % self.test_factor == 0 {
+            let target = if worry_level.is_multiple_of(self.test_factor) {
                 self.true_target
             } else {
                 self.false_target
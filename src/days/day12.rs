@@ -1,13 +1,27 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use crate::util::grid::Grid;
 use crate::util::search::bfs;
 
-pub fn solve_part1(input: RawInput) -> usize {
-    solve(input, b'S')
-}
+pub struct Day12;
+
+impl Day for Day12 {
+    const NUMBER: usize = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> usize {
-    solve(input, b'a')
+    fn part1(&self, input: &RawInput) -> usize {
+        solve(*input, b'S')
+    }
+
+    fn part2(&self, input: &RawInput) -> usize {
+        solve(*input, b'a')
+    }
 }
 
 fn solve(input: RawInput, goal: u8) -> usize {
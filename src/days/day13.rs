@@ -1,32 +1,46 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use serde_json::Value;
 use std::cmp;
 use std::cmp::Ordering;
 use Value::{Array, Number};
 
-pub fn solve_part1(input: RawInput) -> usize {
-    input
-        .grouped_lines(|line| serde_json::from_str::<Value>(line.as_str()).unwrap())
-        .enumerate()
-        .filter(|(_, pair)| cmp_values(&pair[0], &pair[1]) == Ordering::Less)
-        .map(|(i, _)| i + 1)
-        .sum()
-}
+pub struct Day13;
+
+impl Day for Day13 {
+    const NUMBER: usize = 13;
+    const TITLE: &'static str = "Distress Signal";
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> usize {
-    let mut packets: Vec<Value> = input
-        .as_str()
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| serde_json::from_str(line).unwrap())
-        .collect();
-    let sep1: Value = serde_json::from_str("[[2]]").unwrap();
-    let sep2: Value = serde_json::from_str("[[6]]").unwrap();
-    packets.extend([sep1.clone(), sep2.clone()]);
-    packets.sort_by(cmp_values);
-    let i1 = packets.iter().position(|p| p == &sep1).unwrap() + 1;
-    let i2 = packets.iter().position(|p| p == &sep2).unwrap() + 1;
-    i1 * i2
+    fn part1(&self, input: &RawInput) -> usize {
+        input
+            .grouped_lines(|line| serde_json::from_str::<Value>(line.as_str()).unwrap())
+            .enumerate()
+            .filter(|(_, pair)| cmp_values(&pair[0], &pair[1]) == Ordering::Less)
+            .map(|(i, _)| i + 1)
+            .sum()
+    }
+
+    fn part2(&self, input: &RawInput) -> usize {
+        let mut packets: Vec<Value> = input
+            .as_str()
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let sep1: Value = serde_json::from_str("[[2]]").unwrap();
+        let sep2: Value = serde_json::from_str("[[6]]").unwrap();
+        packets.extend([sep1.clone(), sep2.clone()]);
+        packets.sort_by(cmp_values);
+        let i1 = packets.iter().position(|p| p == &sep1).unwrap() + 1;
+        let i2 = packets.iter().position(|p| p == &sep2).unwrap() + 1;
+        i1 * i2
+    }
 }
 
 fn cmp_values(v1: &Value, v2: &Value) -> Ordering {
@@ -1,4 +1,6 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use ndarray::Array2;
 use std::cmp;
 
@@ -7,12 +9,24 @@ use std::cmp;
 // path taken by sand, since the path only differs in the last element from one
 // sand drop to the next.
 
-pub fn solve_part1(input: RawInput) -> usize {
-    solve(input, |pos, max_y| pos[1] < max_y)
-}
+pub struct Day14;
+
+impl Day for Day14 {
+    const NUMBER: usize = 14;
+    const TITLE: &'static str = "Regolith Reservoir";
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> usize {
-    solve(input, |pos, _| pos != [500, 0]) + 1
+    fn part1(&self, input: &RawInput) -> usize {
+        solve(*input, |pos, max_y| pos[1] < max_y)
+    }
+
+    fn part2(&self, input: &RawInput) -> usize {
+        solve(*input, |pos, _| pos != [500, 0]) + 1
+    }
 }
 
 fn solve(input: RawInput, should_continue: impl Fn([usize; 2], usize) -> bool) -> usize {
@@ -1,51 +1,62 @@
-use crate::harness::input::RawInput;
-use crate::regex;
+use crate::harness::day::Day;
+use crate::harness::input::{LineInput, RawInput};
+use crate::unparsed_input;
 use crate::util::coords::Coord2;
-use crate::util::re;
+use std::cmp;
 use std::collections::HashSet;
-use std::str::FromStr;
-use std::{cmp, error};
-
-pub fn solve_part1(input: RawInput) -> usize {
-    let y = 2000000;
-    let readings: Vec<_> = input.per_line(|line| line.single::<Reading>()).collect();
-    let num_beacons_on_row = readings
-        .iter()
-        .filter(|reading| reading.beacon.1 == y)
-        .map(|reading| reading.beacon.0)
-        .collect::<HashSet<_>>()
-        .len();
-    let mut intervals: Vec<_> = readings
-        .into_iter()
-        .filter_map(|reading| reading.to_manhattan_circle().intersect_with_row(y))
-        .collect();
-    let interval_sum: i32 = merge_intervals(&mut intervals)
-        .into_iter()
-        .map(|(min, max)| max - min)
-        .sum();
-    (interval_sum as usize) - num_beacons_on_row
-}
 
-pub fn solve_part2(input: RawInput) -> u64 {
-    let limit = 4000000;
-    let circles: Vec<_> = input
-        .per_line(|line| line.single::<Reading>())
-        .map(Reading::to_manhattan_circle)
-        .collect();
-    for i in 0..circles.len() {
-        for j in i..circles.len() {
-            let intersection = circles[i].intersect_edge(circles[j]);
-            for point in intersection {
-                if (0..=limit).contains(&point.0)
-                    && (0..=limit).contains(&point.1)
-                    && circles.iter().all(|circle| !circle.contains(point))
-                {
-                    return (4000000 * point.0 as u64) + point.1 as u64;
+pub struct Day15;
+
+impl Day for Day15 {
+    const NUMBER: usize = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
+
+    type Output1 = usize;
+    type Output2 = u64;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> usize {
+        let y = 2000000;
+        let readings: Vec<_> = input.per_line(Reading::parse).collect();
+        let num_beacons_on_row = readings
+            .iter()
+            .filter(|reading| reading.beacon.1 == y)
+            .map(|reading| reading.beacon.0)
+            .collect::<HashSet<_>>()
+            .len();
+        let mut intervals: Vec<_> = readings
+            .into_iter()
+            .filter_map(|reading| reading.to_manhattan_circle().intersect_with_row(y))
+            .collect();
+        let interval_sum: i32 = merge_intervals(&mut intervals)
+            .into_iter()
+            .map(|(min, max)| max - min)
+            .sum();
+        (interval_sum as usize) - num_beacons_on_row
+    }
+
+    fn part2(&self, input: &RawInput) -> u64 {
+        let limit = 4000000;
+        let circles: Vec<_> = input
+            .per_line(Reading::parse)
+            .map(Reading::to_manhattan_circle)
+            .collect();
+        for i in 0..circles.len() {
+            for j in i..circles.len() {
+                let intersection = circles[i].intersect_edge(circles[j]);
+                for point in intersection {
+                    if (0..=limit).contains(&point.0)
+                        && (0..=limit).contains(&point.1)
+                        && circles.iter().all(|circle| !circle.contains(point))
+                    {
+                        return (4000000 * point.0 as u64) + point.1 as u64;
+                    }
                 }
             }
         }
+        panic!()
     }
-    panic!()
 }
 
 type Coord = Coord2<i32>;
@@ -56,22 +67,15 @@ struct Reading {
     beacon: Coord,
 }
 
-impl FromStr for Reading {
-    type Err = Box<dyn error::Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (s_x, s_y, b_x, b_y): (i32, i32, i32, i32) = re::parse_with_regex(
-            regex!(r"^Sensor at x=(.?\d+), y=(.?\d+): closest beacon is at x=(.?\d+), y=(.?\d+)$"),
-            s,
-        )?;
-        Ok(Self {
+impl Reading {
+    fn parse(line: LineInput) -> Self {
+        let [s_x, s_y, b_x, b_y] = line.ints_array();
+        Self {
             sensor: Coord2(s_x, s_y),
             beacon: Coord2(b_x, b_y),
-        })
+        }
     }
-}
 
-impl Reading {
     fn to_manhattan_circle(self) -> ManhattanCircle {
         ManhattanCircle {
             center: self.sensor,
@@ -1,39 +1,50 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
-use crate::regex;
 use crate::util::re;
-use crate::util::re::Str;
+use crate::{captures_struct, regex};
 use arrayvec::ArrayString;
 use ndarray::{Array2, Array3};
 use std::str::FromStr;
 use std::{cmp, error};
 
-pub fn solve_part1(input: RawInput) -> u32 {
-    let layout = parse_layout(input);
-    get_best_scores_by_bitset(&layout, 30)
-        .into_iter()
-        .max()
-        .unwrap()
-}
+pub struct Day16;
+
+impl Day for Day16 {
+    const NUMBER: usize = 16;
+    const TITLE: &'static str = "Proboscidea Volcanium";
+
+    type Parsed<'a> = Layout;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(&self, input: RawInput) -> Result<Layout, Box<dyn error::Error>> {
+        let valves = input
+            .try_per_line(|line| line.try_single::<Valve>())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Layout::from_valves(&valves))
+    }
+
+    fn part1(&self, layout: &Layout) -> u32 {
+        get_best_scores_by_bitset(layout, 30)
+            .into_iter()
+            .max()
+            .unwrap()
+    }
 
-pub fn solve_part2(input: RawInput) -> u32 {
-    let layout = parse_layout(input);
-    let scores_by_bitset = get_best_scores_by_bitset(&layout, 26);
-    let bitset_max = 1 << layout.n_valves();
-    let mut best = 0;
-    for i in 0..bitset_max {
-        for j in i + 1..bitset_max {
-            if i & j == 0 {
-                let score = scores_by_bitset[i] + scores_by_bitset[j];
-                best = cmp::max(best, score);
+    fn part2(&self, layout: &Layout) -> u32 {
+        let scores_by_bitset = get_best_scores_by_bitset(layout, 26);
+        let bitset_max = 1 << layout.n_valves();
+        let mut best = 0;
+        for i in 0..bitset_max {
+            for j in i + 1..bitset_max {
+                if i & j == 0 {
+                    let score = scores_by_bitset[i] + scores_by_bitset[j];
+                    best = cmp::max(best, score);
+                }
             }
         }
+        best
     }
-    best
-}
-
-fn parse_layout(input: RawInput) -> Layout {
-    let valves: Vec<_> = input.per_line(|line| line.single::<Valve>()).collect();
-    Layout::from_valves(&valves)
 }
 
 fn get_best_scores_by_bitset(layout: &Layout, time_limit: usize) -> Vec<u32> {
@@ -79,30 +90,25 @@ fn get_best_scores_by_bitset(layout: &Layout, time_limit: usize) -> Vec<u32> {
 
 type Label = ArrayString<2>;
 
-#[derive(Debug)]
-struct Valve {
-    label: Label,
-    flow_rate: u32,
-    connections: Vec<Label>,
+captures_struct! {
+    #[derive(Debug)]
+    struct Valve {
+        label: Label,
+        flow_rate: u32,
+        connections: Vec<Label>,
+    }
 }
 
 impl FromStr for Valve {
     type Err = Box<dyn error::Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (label, flow_rate, Str(tunnels)): (Label, u32, Str) = re::parse_with_regex(
-            regex!(r"^Valve (..) has flow rate=(\d+); tunnels? leads? to valves? (.+)$"),
+        re::parse_with_regex(
+            regex!(
+                r"^Valve (?P<label>..) has flow rate=(?P<flow_rate>\d+); tunnels? leads? to valves? (?P<connections>.+)$"
+            ),
             s,
-        )?;
-        let connections = tunnels
-            .split(", ")
-            .map(|s| Label::from(s).unwrap())
-            .collect();
-        Ok(Self {
-            label,
-            flow_rate,
-            connections,
-        })
+        )
     }
 }
 
@@ -116,7 +122,7 @@ struct UsefulValve {
 }
 
 #[derive(Debug)]
-struct Layout {
+pub struct Layout {
     valves: Vec<UsefulValve>,
     distances: Array2<usize>,
 }
@@ -1,35 +1,49 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use crate::util::coords::Coord2;
 use std::cmp;
 use std::collections::{HashMap, HashSet};
 
-pub fn solve_part1(input: RawInput) -> i32 {
-    let mut state = State::new(parse_movements(input));
-    for _ in 0..2022 {
-        state.drop_block();
+pub struct Day17;
+
+impl Day for Day17 {
+    const NUMBER: usize = 17;
+    const TITLE: &'static str = "Pyroclastic Flow";
+
+    type Output1 = i32;
+    type Output2 = u64;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> i32 {
+        let mut state = State::new(parse_movements(*input));
+        for _ in 0..2022 {
+            state.drop_block();
+        }
+        state.max_height
     }
-    state.max_height
-}
 
-pub fn solve_part2(input: RawInput) -> u64 {
-    let mut state = State::new(parse_movements(input));
-    let limit: u64 = 1000000000000;
-    let CycleParams {
-        block_count,
-        height,
-        cycle_length,
-        height_gain_per_cycle,
-    } = find_cycle_params(&mut state);
-    state.reset();
-    let remaining_step_count = limit - block_count;
-    let complete_cycles = remaining_step_count / cycle_length;
-    let height_after_cycles = height + complete_cycles * height_gain_per_cycle;
-    let leftover_steps = remaining_step_count % cycle_length;
-    for _ in 0..(block_count + leftover_steps) {
-        state.drop_block();
+    fn part2(&self, input: &RawInput) -> u64 {
+        let mut state = State::new(parse_movements(*input));
+        let limit: u64 = 1000000000000;
+        let CycleParams {
+            block_count,
+            height,
+            cycle_length,
+            height_gain_per_cycle,
+        } = find_cycle_params(&mut state);
+        state.reset();
+        let remaining_step_count = limit - block_count;
+        let complete_cycles = remaining_step_count / cycle_length;
+        let height_after_cycles = height + complete_cycles * height_gain_per_cycle;
+        let leftover_steps = remaining_step_count % cycle_length;
+        for _ in 0..(block_count + leftover_steps) {
+            state.drop_block();
+        }
+        let leftover_height_gain = state.max_height as u64 - height;
+        height_after_cycles + leftover_height_gain
     }
-    let leftover_height_gain = state.max_height as u64 - height;
-    height_after_cycles + leftover_height_gain
 }
 
 #[derive(Debug, Copy, Clone)]
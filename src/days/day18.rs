@@ -1,49 +1,63 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use arrayvec::ArrayVec;
 use ndarray::Array3;
 
-pub fn solve_part1(input: RawInput) -> usize {
-    let cubes = parse_cubes_into_3d_grid(input);
-    let bounds = cubes.dim();
-    let mut surface_area = 0;
-    for x in 0..bounds.0 {
-        for y in 0..bounds.1 {
-            for z in 0..bounds.2 {
-                let loc = [x, y, z];
-                if cubes[loc] {
-                    let neighbor_count = get_orthogonal_neighbors(loc, bounds)
-                        .into_iter()
-                        .filter(|&neighbor| cubes[neighbor])
-                        .count();
-                    surface_area += 6 - neighbor_count;
+pub struct Day18;
+
+impl Day for Day18 {
+    const NUMBER: usize = 18;
+    const TITLE: &'static str = "Boiling Boulders";
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> usize {
+        let cubes = parse_cubes_into_3d_grid(*input);
+        let bounds = cubes.dim();
+        let mut surface_area = 0;
+        for x in 0..bounds.0 {
+            for y in 0..bounds.1 {
+                for z in 0..bounds.2 {
+                    let loc = [x, y, z];
+                    if cubes[loc] {
+                        let neighbor_count = get_orthogonal_neighbors(loc, bounds)
+                            .into_iter()
+                            .filter(|&neighbor| cubes[neighbor])
+                            .count();
+                        surface_area += 6 - neighbor_count;
+                    }
                 }
             }
         }
+        surface_area
     }
-    surface_area
-}
 
-pub fn solve_part2(input: RawInput) -> usize {
-    let cubes = parse_cubes_into_3d_grid(input);
-    let mut seen_cubes = cubes.clone();
-    let bounds = seen_cubes.dim();
-    let mut pending = vec![[0, 0, 0]];
-    let mut surface_area = 0;
-    while let Some(current) = pending.pop() {
-        if seen_cubes[current] {
-            continue;
-        }
-        seen_cubes[current] = true;
-        for neighbor in get_orthogonal_neighbors(current, bounds) {
-            if cubes[neighbor] {
-                surface_area += 1;
+    fn part2(&self, input: &RawInput) -> usize {
+        let cubes = parse_cubes_into_3d_grid(*input);
+        let mut seen_cubes = cubes.clone();
+        let bounds = seen_cubes.dim();
+        let mut pending = vec![[0, 0, 0]];
+        let mut surface_area = 0;
+        while let Some(current) = pending.pop() {
+            if seen_cubes[current] {
+                continue;
             }
-            if !seen_cubes[neighbor] {
-                pending.push(neighbor);
+            seen_cubes[current] = true;
+            for neighbor in get_orthogonal_neighbors(current, bounds) {
+                if cubes[neighbor] {
+                    surface_area += 1;
+                }
+                if !seen_cubes[neighbor] {
+                    pending.push(neighbor);
+                }
             }
         }
+        surface_area
     }
-    surface_area
 }
 
 fn parse_cubes_into_3d_grid(input: RawInput) -> Array3<bool> {
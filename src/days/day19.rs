@@ -1,22 +1,34 @@
-use crate::harness::input::RawInput;
-use crate::regex;
-use crate::util::re;
+use crate::harness::day::Day;
+use crate::harness::input::{LineInput, RawInput};
+use crate::unparsed_input;
 use std::cmp;
 
-pub fn solve_part1(input: RawInput) -> u32 {
-    input
-        .per_line(|line| parse_blueprint_costs(line.as_str()))
-        .enumerate()
-        .map(|(i, costs)| (i as u32 + 1) * get_max_geodes(costs, 24))
-        .sum()
-}
+pub struct Day19;
+
+impl Day for Day19 {
+    const NUMBER: usize = 19;
+    const TITLE: &'static str = "Not Enough Minerals";
+
+    type Output1 = u32;
+    type Output2 = u32;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> u32 {
-    input
-        .per_line(|line| parse_blueprint_costs(line.as_str()))
-        .take(3)
-        .map(|costs| get_max_geodes(costs, 32))
-        .product()
+    fn part1(&self, input: &RawInput) -> u32 {
+        input
+            .per_line(parse_blueprint_costs)
+            .enumerate()
+            .map(|(i, costs)| (i as u32 + 1) * get_max_geodes(costs, 24))
+            .sum()
+    }
+
+    fn part2(&self, input: &RawInput) -> u32 {
+        input
+            .per_line(parse_blueprint_costs)
+            .take(3)
+            .map(|costs| get_max_geodes(costs, 32))
+            .product()
+    }
 }
 
 fn get_max_geodes(costs: [[u32; 4]; 4], time_limit: u32) -> u32 {
@@ -81,8 +93,8 @@ struct State {
 impl State {
     fn get_wait_time(self, costs: [u32; 4]) -> Option<u32> {
         let mut wait_time = 0;
-        for i in 0..4 {
-            let missing = costs[i].saturating_sub(self.resources[i]);
+        for (i, &cost) in costs.iter().enumerate() {
+            let missing = cost.saturating_sub(self.resources[i]);
             if missing == 0 {
                 continue;
             }
@@ -104,13 +116,9 @@ impl State {
 
 /// Returns an array of costs, where `costs[i][j]` is the amount of resource `j`
 /// required to build a robot of type `i`.
-fn parse_blueprint_costs(s: &str) -> [[u32; 4]; 4] {
-    let (ore_for_ore, ore_for_clay, ore_for_obsidian, clay_for_obsidian, ore_for_geode, obsidian_for_geode) : (u32, u32, u32, u32, u32, u32) = re::parse_with_regex(
-        regex!(
-                r"^Blueprint \d+: Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.$"
-            ),
-        s
-    ).unwrap();
+fn parse_blueprint_costs(line: LineInput) -> [[u32; 4]; 4] {
+    let [_, ore_for_ore, ore_for_clay, ore_for_obsidian, clay_for_obsidian, ore_for_geode, obsidian_for_geode] =
+        line.ints_array();
     let mut out: [[u32; 4]; 4] = Default::default();
     out[0][0] = ore_for_ore;
     out[1][0] = ore_for_clay;
@@ -122,5 +130,5 @@ fn parse_blueprint_costs(s: &str) -> [[u32; 4]; 4] {
 }
 
 fn div_round_up(a: u32, b: u32) -> u32 {
-    (a + b - 1) / b
+    a.div_ceil(b)
 }
@@ -1,19 +1,33 @@
+use crate::harness::day::Day;
 use crate::harness::input::{LineInput, RawInput};
+use crate::unparsed_input;
 
-pub fn solve_part1(input: RawInput) -> u32 {
-    input
-        .per_line(parse_to_values)
-        .map(|(their_move, our_move)| score_moves(their_move, our_move))
-        .sum()
-}
+pub struct Day2;
+
+impl Day for Day2 {
+    const NUMBER: usize = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Output1 = u32;
+    type Output2 = u32;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> u32 {
+        input
+            .per_line(parse_to_values)
+            .map(|(their_move, our_move)| score_moves(their_move, our_move))
+            .sum()
+    }
 
-pub fn solve_part2(input: RawInput) -> u32 {
-    input
-        .per_line(parse_to_values)
-        .map(|(their_move, desired_result)| {
-            score_moves(their_move, required_move(their_move, desired_result))
-        })
-        .sum()
+    fn part2(&self, input: &RawInput) -> u32 {
+        input
+            .per_line(parse_to_values)
+            .map(|(their_move, desired_result)| {
+                score_moves(their_move, required_move(their_move, desired_result))
+            })
+            .sum()
+    }
 }
 
 fn parse_to_values(line: LineInput) -> (u8, u8) {
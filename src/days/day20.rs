@@ -1,11 +1,25 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 
-pub fn solve_part1(input: RawInput) -> i64 {
-    solve(input, 1, 1)
-}
+pub struct Day20;
+
+impl Day for Day20 {
+    const NUMBER: usize = 20;
+    const TITLE: &'static str = "Grove Positioning System";
+
+    type Output1 = i64;
+    type Output2 = i64;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> i64 {
-   solve(input, 811589153, 10)
+    fn part1(&self, input: &RawInput) -> i64 {
+        solve(*input, 1, 1)
+    }
+
+    fn part2(&self, input: &RawInput) -> i64 {
+        solve(*input, 811589153, 10)
+    }
 }
 
 fn solve(input: RawInput, multiplier: i64, num_rounds: usize) -> i64 {
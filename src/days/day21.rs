@@ -1,22 +1,40 @@
-use crate::harness::input::RawInput;
+use crate::harness::day::Day;
+use crate::harness::input::{InputError, RawInput};
+use crate::unparsed_input;
 use arrayvec::{ArrayString, ArrayVec};
 use std::collections::HashMap;
 use std::error;
 use std::str::FromStr;
 
-pub fn solve_part1(input: RawInput) -> i64 {
-    let monkeys: Vec<_> = input.per_line(|line| line.single::<Monkey>()).collect();
-    solve(&monkeys, "root")
+pub struct Day21;
+
+impl Day for Day21 {
+    const NUMBER: usize = 21;
+    const TITLE: &'static str = "Monkey Math";
+
+    type Output1 = Result<i64, Box<dyn error::Error>>;
+    type Output2 = Result<i64, Box<dyn error::Error>>;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> Result<i64, Box<dyn error::Error>> {
+        let monkeys = parse_monkeys(*input)?;
+        solve(&monkeys, "root")
+    }
+
+    fn part2(&self, input: &RawInput) -> Result<i64, Box<dyn error::Error>> {
+        let monkeys = get_part2_monkeys(*input)?;
+        solve(&monkeys, "humn")
+    }
 }
 
-pub fn solve_part2(input: RawInput) -> i64 {
-    let monkeys = get_part2_monkeys(input);
-    solve(&monkeys, "humn")
+fn parse_monkeys(input: RawInput) -> Result<Vec<Monkey>, InputError> {
+    input.try_per_line(|line| line.try_single()).collect()
 }
 
-fn get_part2_monkeys(input: RawInput) -> Vec<Monkey> {
+fn get_part2_monkeys(input: RawInput) -> Result<Vec<Monkey>, Box<dyn error::Error>> {
     let mut monkeys = vec![];
-    for monkey in input.per_line(|line| line.single::<Monkey>()) {
+    for monkey in parse_monkeys(input)? {
         match monkey.label.as_str() {
             "root" => {
                 if let Action::Combine(_, label1, label2) = monkey.action {
@@ -25,7 +43,7 @@ fn get_part2_monkeys(input: RawInput) -> Vec<Monkey> {
                         Monkey::new(label2, Action::Watch(label1)),
                     ]);
                 } else {
-                    panic!("Root monkey was not watching other monkeys.");
+                    Err("Root monkey was not watching other monkeys.")?
                 }
             }
             "humn" => (),
@@ -35,10 +53,10 @@ fn get_part2_monkeys(input: RawInput) -> Vec<Monkey> {
             }
         }
     }
-    monkeys
+    Ok(monkeys)
 }
 
-fn solve(monkeys: &[Monkey], root: &str) -> i64 {
+fn solve(monkeys: &[Monkey], root: &str) -> Result<i64, Box<dyn error::Error>> {
     let mut monkeys_by_child = HashMap::<Label, ArrayVec<Monkey, 4>>::new();
     for &monkey in monkeys {
         match monkey.action {
@@ -64,13 +82,13 @@ fn solve(monkeys: &[Monkey], root: &str) -> i64 {
         }
         if let Some(value) = monkey.action.evaluate(&values_by_label) {
             if &monkey.label == root {
-                return value;
+                return Ok(value);
             }
             values_by_label.insert(monkey.label, value);
             pending.extend(&monkeys_by_child[&monkey.label]);
         }
     }
-    panic!("Could not evaluate root.");
+    Err("Could not evaluate root.")?
 }
 
 type Label = ArrayString<4>;
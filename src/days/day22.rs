@@ -1,6 +1,8 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
 use crate::regex;
 use ndarray::Array2;
+use std::error;
 use Direction::{Down, Left, Right, Up};
 use Move::{Forward, TurnLeft, TurnRight};
 use Tile::{Blocked, Open, Warp};
@@ -18,34 +20,53 @@ static PORTALS: [Portal; 7] = [
 const EDGE_LENGTH: usize = 50;
 
 
-pub fn solve_part1(input: RawInput) -> usize {
-    solve(input, false)
+pub struct Day22;
+
+impl Day for Day22 {
+    const NUMBER: usize = 22;
+    const TITLE: &'static str = "Monkey Map";
+
+    type Parsed<'a> = Notes;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input: RawInput) -> Result<Notes, Box<dyn error::Error>> {
+        parse(input)
+    }
+
+    fn part1(&self, notes: &Notes) -> usize {
+        solve(notes, false)
+    }
+
+    fn part2(&self, notes: &Notes) -> usize {
+        solve(notes, true)
+    }
 }
 
-pub fn solve_part2(input: RawInput) -> usize {
-    solve(input, true)
+pub struct Notes {
+    board: Array2<Tile>,
+    moves: Vec<Move>,
 }
 
-fn solve(input: RawInput, is_part2: bool) -> usize {
-    let (board, moves) = parse(input);
-    let mut state = State::new(board, is_part2);
-    for moove in moves {
+fn solve(notes: &Notes, is_part2: bool) -> usize {
+    let mut state = State::new(&notes.board, is_part2);
+    for &moove in &notes.moves {
         state.apply_move(moove);
     }
     state.position.score()
 }
 
 #[derive(Debug)]
-struct State {
-    board: Array2<Tile>,
+struct State<'a> {
+    board: &'a Array2<Tile>,
     position: Position,
     is_part_2: bool,
 }
 
-impl State {
-    fn new(board: Array2<Tile>, is_part_2: bool) -> Self {
+impl<'a> State<'a> {
+    fn new(board: &'a Array2<Tile>, is_part_2: bool) -> Self {
         Self {
-            position: get_start_position(&board),
+            position: get_start_position(board),
             board,
             is_part_2,
         }
@@ -308,18 +329,18 @@ fn parse_moves(s: &str) -> Vec<Move> {
         })
         .collect();
     let mut out = vec![];
-    for i in 0..numbers.len() {
-        out.push(numbers[i]);
+    for (i, &number) in numbers.iter().enumerate() {
+        out.push(number);
         out.extend(turns.get(i));
     }
     out
 }
 
-fn parse(input: RawInput) -> (Array2<Tile>, Vec<Move>) {
-    let (board, moves) = input.as_str().trim_end().split_once("\n\n").unwrap();
-    let board = parse_board(board);
-    let moves = parse_moves(moves);
-    (board, moves)
+fn parse(input: RawInput) -> Result<Notes, Box<dyn error::Error>> {
+    let (board, moves): (RawInput, RawInput) = input.parse_sections()?;
+    let board = parse_board(board.as_str());
+    let moves = parse_moves(moves.as_str());
+    Ok(Notes { board, moves })
 }
 
 fn get_start_position(board: &Array2<Tile>) -> Position {
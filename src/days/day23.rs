@@ -1,29 +1,43 @@
 use std::collections::{HashMap, HashSet};
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use crate::util::coords::Coord2;
 
-pub fn solve_part1(input: RawInput) -> i32 {
-    let mut positions = parse_positions(input);
-    for round in 0..10 {
-        positions = advance(&positions, round);
+pub struct Day23;
+
+impl Day for Day23 {
+    const NUMBER: usize = 23;
+    const TITLE: &'static str = "Unstable Diffusion";
+
+    type Output1 = i32;
+    type Output2 = u32;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> i32 {
+        let mut positions = parse_positions(*input);
+        for round in 0..10 {
+            positions = advance(&positions, round);
+        }
+        let min_x = positions.iter().map(|c| c.0).min().unwrap();
+        let max_x = positions.iter().map(|c| c.0).max().unwrap();
+        let min_y = positions.iter().map(|c| c.1).min().unwrap();
+        let max_y = positions.iter().map(|c| c.1).max().unwrap();
+        (max_x - min_x + 1) * (max_y - min_y + 1) - positions.len() as i32
     }
-    let min_x = positions.iter().map(|c| c.0).min().unwrap();
-    let max_x = positions.iter().map(|c| c.0).max().unwrap();
-    let min_y = positions.iter().map(|c| c.1).min().unwrap();
-    let max_y = positions.iter().map(|c| c.1).max().unwrap();
-    (max_x - min_x + 1) * (max_y - min_y + 1) - positions.len() as i32
-}
 
-pub fn solve_part2(input: RawInput) -> u32 {
-    let mut positions = parse_positions(input);
-    for round in 0.. {
-        let new_positions = advance(&positions, round);
-        if new_positions == positions{
-            return round + 1;
+    fn part2(&self, input: &RawInput) -> u32 {
+        let mut positions = parse_positions(*input);
+        for round in 0.. {
+            let new_positions = advance(&positions, round);
+            if new_positions == positions{
+                return round + 1;
+            }
+            positions = new_positions;
         }
-        positions = new_positions;
+        unreachable!()
     }
-    unreachable!()
 }
 
 type Coord = Coord2<i32>;
@@ -1,3 +1,4 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
 use crate::util::grid::Grid;
 use crate::util::search::bfs;
@@ -7,22 +8,35 @@ use std::str::FromStr;
 
 const START_LOCATION: Index = [0, 1];
 
-pub fn solve_part1(input: RawInput) -> usize {
-    let blizzards_at_times = load_blizzards_at_times(input);
-    get_shortest_time(
-        &blizzards_at_times,
-        START_LOCATION,
-        blizzards_at_times[0].end_location(),
-        0,
-    )
-}
+pub struct Day24;
+
+impl Day for Day24 {
+    const NUMBER: usize = 24;
+    const TITLE: &'static str = "Blizzard Basin";
+
+    type Parsed<'a> = Vec<Blizzards>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input: RawInput) -> Result<Vec<Blizzards>, Box<dyn error::Error>> {
+        load_blizzards_at_times(input)
+    }
+
+    fn part1(&self, blizzards_at_times: &Vec<Blizzards>) -> usize {
+        get_shortest_time(
+            blizzards_at_times,
+            START_LOCATION,
+            blizzards_at_times[0].end_location(),
+            0,
+        )
+    }
 
-pub fn solve_part2(input: RawInput) -> usize {
-    let blizzards_at_times = load_blizzards_at_times(input);
-    let end_location = blizzards_at_times[0].end_location();
-    let time = get_shortest_time(&blizzards_at_times, START_LOCATION, end_location, 0);
-    let time = get_shortest_time(&blizzards_at_times, end_location, START_LOCATION, time);
-    get_shortest_time(&blizzards_at_times, START_LOCATION, end_location, time)
+    fn part2(&self, blizzards_at_times: &Vec<Blizzards>) -> usize {
+        let end_location = blizzards_at_times[0].end_location();
+        let time = get_shortest_time(blizzards_at_times, START_LOCATION, end_location, 0);
+        let time = get_shortest_time(blizzards_at_times, end_location, START_LOCATION, time);
+        get_shortest_time(blizzards_at_times, START_LOCATION, end_location, time)
+    }
 }
 
 fn get_shortest_time(
@@ -60,7 +74,7 @@ fn get_shortest_time(
 type Index = [usize; 2];
 
 #[derive(Debug)]
-struct Blizzards {
+pub struct Blizzards {
     right_blizzards: Grid<bool>,
     up_blizzards: Grid<bool>,
     left_blizzards: Grid<bool>,
@@ -148,8 +162,8 @@ impl Blizzards {
     }
 }
 
-fn load_blizzards_at_times(input: RawInput) -> Vec<Blizzards> {
-    let mut blizzards: Blizzards = input.as_str().parse().unwrap();
+fn load_blizzards_at_times(input: RawInput) -> Result<Vec<Blizzards>, Box<dyn error::Error>> {
+    let mut blizzards: Blizzards = input.as_str().parse()?;
     let mut blizzards_at_time: Vec<Blizzards> = vec![];
     let cycle_length = integer::lcm(blizzards.nrows() - 2, blizzards.ncols() - 2);
     for _ in 0..cycle_length {
@@ -157,5 +171,5 @@ fn load_blizzards_at_times(input: RawInput) -> Vec<Blizzards> {
         blizzards_at_time.push(blizzards);
         blizzards = next_blizzards;
     }
-    blizzards_at_time
+    Ok(blizzards_at_time)
 }
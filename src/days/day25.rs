@@ -1,16 +1,36 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::harness::test_input::TestInput;
+use crate::unparsed_input;
 
 const DIGITS: &[u8] = b"=-012";
 
-pub fn solve_part1(input: RawInput) -> String {
-    let sum: i64 = input
-        .per_line(|line| from_snafu(line.as_str().as_bytes()))
-        .sum();
-    to_snafu(sum)
-}
+pub struct Day25;
+
+impl Day for Day25 {
+    const NUMBER: usize = 25;
+    const TITLE: &'static str = "Full of Hot Air";
+    const TEST_INPUTS: &'static [TestInput<'static>] = &[
+        TestInput::inline("round-trip", "2=-01\n", Some("2=-01"), None),
+        TestInput::inline("carry", "2\n2\n1\n", Some("10"), None),
+    ];
+    const PART2_EXISTS: bool = false;
+
+    type Output1 = String;
+    type Output2 = usize;
+
+    unparsed_input!();
 
-pub fn solve_part2(_: RawInput) -> usize {
-    todo!()
+    fn part1(&self, input: &RawInput) -> String {
+        let sum: i64 = input
+            .per_line(|line| from_snafu(line.as_str().as_bytes()))
+            .sum();
+        to_snafu(sum)
+    }
+
+    fn part2(&self, _: &RawInput) -> usize {
+        unreachable!("Day 25 has no part 2.")
+    }
 }
 
 fn from_snafu(bs: &[u8]) -> i64 {
@@ -1,25 +1,38 @@
+use crate::harness::day::Day;
+use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use std::collections::HashSet;
 
-use crate::harness::input::RawInput;
+pub struct Day3;
 
-pub fn solve_part1(input: RawInput) -> u32 {
-    input
-        .as_str()
-        .lines()
-        .map(intersect_string_halves)
-        .map(priority)
-        .sum()
-}
+impl Day for Day3 {
+    const NUMBER: usize = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Output1 = u32;
+    type Output2 = u32;
 
-pub fn solve_part2(input: RawInput) -> u32 {
-    input
-        .as_str()
-        .lines()
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .map(intersect_strs)
-        .map(priority)
-        .sum()
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> u32 {
+        input
+            .as_str()
+            .lines()
+            .map(intersect_string_halves)
+            .map(priority)
+            .sum()
+    }
+
+    fn part2(&self, input: &RawInput) -> u32 {
+        input
+            .as_str()
+            .lines()
+            .collect::<Vec<_>>()
+            .chunks(3)
+            .map(intersect_strs)
+            .map(priority)
+            .sum()
+    }
 }
 
 fn intersect_string_halves(s: &str) -> u8 {
@@ -1,24 +1,40 @@
-use crate::harness::input::RawInput;
+use crate::harness::day::Day;
+use crate::harness::input::{InputError, RawInput};
 use crate::regex;
+use crate::unparsed_input;
 
-pub fn solve_part1(input: RawInput) -> usize {
-    input
-        .per_line(|line| {
-            line.parse_with_regex::<(u32, u32, u32, u32)>(regex!(r"(\d+)-(\d+),(\d+)-(\d+)"))
-        })
-        .filter(|&(start1, end1, start2, end2)| {
-            (start1 <= start2 && end1 >= end2) || (start2 <= start1 && end2 >= end1)
-        })
-        .count()
+pub struct Day4;
+
+impl Day for Day4 {
+    const NUMBER: usize = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Output1 = Result<usize, InputError>;
+    type Output2 = Result<usize, InputError>;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> Result<usize, InputError> {
+        Ok(parse_pairs(*input)?
+            .into_iter()
+            .filter(|&(start1, end1, start2, end2)| {
+                (start1 <= start2 && end1 >= end2) || (start2 <= start1 && end2 >= end1)
+            })
+            .count())
+    }
+
+    fn part2(&self, input: &RawInput) -> Result<usize, InputError> {
+        Ok(parse_pairs(*input)?
+            .into_iter()
+            .filter(|&(start1, end1, start2, end2)| {
+                (start1 <= start2 && end1 >= start2) || (start2 <= start1 && end2 >= start1)
+            })
+            .count())
+    }
 }
 
-pub fn solve_part2(input: RawInput) -> usize {
+fn parse_pairs(input: RawInput) -> Result<Vec<(u32, u32, u32, u32)>, InputError> {
     input
-        .per_line(|line| {
-            line.parse_with_regex::<(u32, u32, u32, u32)>(regex!(r"(\d+)-(\d+),(\d+)-(\d+)"))
-        })
-        .filter(|&(start1, end1, start2, end2)| {
-            (start1 <= start2 && end1 >= start2) || (start2 <= start1 && end2 >= start1)
-        })
-        .count()
+        .try_per_line(|line| line.try_parse_with_regex(regex!(r"(\d+)-(\d+),(\d+)-(\d+)")))
+        .collect()
 }
@@ -1,23 +1,36 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
-use crate::regex;
-use crate::util::re;
+use crate::pattern_struct;
+use crate::unparsed_input;
 use std::error;
 use std::str::FromStr;
 
-pub fn solve_part1(input: RawInput) -> String {
-    let Input { mut crates, moves } = input.as_str().parse().unwrap();
-    for moove in moves {
-        crates.apply_part1_move(moove);
+pub struct Day5;
+
+impl Day for Day5 {
+    const NUMBER: usize = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    type Output1 = Result<String, Box<dyn error::Error>>;
+    type Output2 = Result<String, Box<dyn error::Error>>;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> Result<String, Box<dyn error::Error>> {
+        let Input { mut crates, moves } = Input::parse(*input)?;
+        for moove in moves {
+            crates.apply_part1_move(moove);
+        }
+        Ok(crates.read_top_crates())
     }
-    crates.read_top_crates()
-}
 
-pub fn solve_part2(input: RawInput) -> String {
-    let Input { mut crates, moves } = input.as_str().parse().unwrap();
-    for moove in moves {
-        crates.apply_part2_move(moove);
+    fn part2(&self, input: &RawInput) -> Result<String, Box<dyn error::Error>> {
+        let Input { mut crates, moves } = Input::parse(*input)?;
+        for moove in moves {
+            crates.apply_part2_move(moove);
+        }
+        Ok(crates.read_top_crates())
     }
-    crates.read_top_crates()
 }
 
 #[derive(Debug)]
@@ -26,16 +39,12 @@ struct Input {
     moves: Vec<Move>,
 }
 
-impl FromStr for Input {
-    type Err = Box<dyn error::Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (crates_str, moves_str) = s.split_once("\n\n").ok_or("Could not split.")?;
-        let crates = crates_str.parse()?;
-        let mut moves = vec![];
-        for line in moves_str.lines() {
-            moves.push(line.parse()?);
-        }
+impl Input {
+    fn parse(input: RawInput) -> Result<Self, Box<dyn error::Error>> {
+        let (crates, moves): (Crates, RawInput) = input.parse_sections()?;
+        let moves = moves
+            .try_per_line(|line| line.try_single())
+            .collect::<Result<_, _>>()?;
         Ok(Self { crates, moves })
     }
 }
@@ -48,7 +57,7 @@ impl FromStr for Crates {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let rows: Vec<_> = s.lines().rev().skip(1).map(parse_crate_row).collect();
-        let ncols = rows.first().unwrap().len();
+        let ncols = rows.first().ok_or("No crates.")?.len();
         let mut result = vec![Vec::new(); ncols];
         for row in rows {
             for (i, char) in row {
@@ -62,15 +71,15 @@ impl FromStr for Crates {
 impl Crates {
     fn apply_part1_move(&mut self, Move { count, start, end }: Move) {
         for _ in 0..count {
-            let crait = self.0[start].pop().unwrap();
-            self.0[end].push(crait);
+            let crait = self.0[start - 1].pop().unwrap();
+            self.0[end - 1].push(crait);
         }
     }
 
     fn apply_part2_move(&mut self, Move { count, start, end }: Move) {
-        let start_col = &mut self.0[start];
+        let start_col = &mut self.0[start - 1];
         let crates = start_col.split_off(start_col.len() - count);
-        self.0[end].extend(crates);
+        self.0[end - 1].extend(crates);
     }
 
     fn read_top_crates(&self) -> String {
@@ -81,24 +90,14 @@ impl Crates {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Move {
-    count: usize,
-    start: usize,
-    end: usize,
-}
-
-impl FromStr for Move {
-    type Err = Box<dyn error::Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (count, start, end): (usize, usize, usize) =
-            re::parse_with_regex(regex!(r"^move (\d+) from (\d+) to (\d+)$"), s)?;
-        Ok(Self {
-            count,
-            start: start - 1,
-            end: end - 1,
-        })
+pattern_struct! {
+    #[pattern("move {count} from {start} to {end}")]
+    #[derive(Debug, Copy, Clone)]
+    struct Move {
+        count: usize,
+        /// The 1-based number of the stack to move from.
+        start: usize,
+        end: usize,
     }
 }
 
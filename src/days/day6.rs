@@ -1,11 +1,25 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 
-pub fn solve_part1(input: RawInput) -> usize {
-    solve(input, 4)
-}
+pub struct Day6;
+
+impl Day for Day6 {
+    const NUMBER: usize = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> usize {
-    solve(input, 14)
+    fn part1(&self, input: &RawInput) -> usize {
+        solve(*input, 4)
+    }
+
+    fn part2(&self, input: &RawInput) -> usize {
+        solve(*input, 14)
+    }
 }
 
 // Longer solution optimized for speed.
@@ -1,23 +1,37 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use std::collections::HashMap;
 
-pub fn solve_part1(input: RawInput) -> u32 {
-    infer_filesystem(input)
-        .get_directory_sizes()
-        .into_iter()
-        .filter(|&size| size <= 100000)
-        .sum()
-}
+pub struct Day7;
+
+impl Day for Day7 {
+    const NUMBER: usize = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    type Output1 = u32;
+    type Output2 = u32;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> u32 {
-    let sizes = infer_filesystem(input).get_directory_sizes();
-    let root_size = sizes[0];
-    let space_to_free = root_size - 40000000;
-    sizes
-        .into_iter()
-        .filter(|&size| size >= space_to_free)
-        .min()
-        .unwrap()
+    fn part1(&self, input: &RawInput) -> u32 {
+        infer_filesystem(*input)
+            .get_directory_sizes()
+            .into_iter()
+            .filter(|&size| size <= 100000)
+            .sum()
+    }
+
+    fn part2(&self, input: &RawInput) -> u32 {
+        let sizes = infer_filesystem(*input).get_directory_sizes();
+        let root_size = sizes[0];
+        let space_to_free = root_size - 40000000;
+        sizes
+            .into_iter()
+            .filter(|&size| size >= space_to_free)
+            .min()
+            .unwrap()
+    }
 }
 
 fn infer_filesystem(input: RawInput) -> FileSystem {
@@ -1,40 +1,54 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use crate::util::grid::Grid;
 use std::cmp;
 
-pub fn solve_part1(input: RawInput) -> usize {
-    let heights = Grid::parse_digits(input.as_str());
-    let mut views = initialize_views(&heights);
-    heights
-        .indices()
-        .filter(|&ij| {
-            let height = heights[ij];
-            fill_views(&mut views, &heights, ij);
-            views.iter().any(|view| view.iter().all(|&h| h < height))
-        })
-        .count()
-}
+pub struct Day8;
+
+impl Day for Day8 {
+    const NUMBER: usize = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> usize {
+        let heights = Grid::parse_digits(input.as_str());
+        let mut views = initialize_views(&heights);
+        heights
+            .indices()
+            .filter(|&ij| {
+                let height = heights[ij];
+                fill_views(&mut views, &heights, ij);
+                views.iter().any(|view| view.iter().all(|&h| h < height))
+            })
+            .count()
+    }
 
-pub fn solve_part2(input: RawInput) -> usize {
-    let heights = Grid::parse_digits(input.as_str());
-    let mut views = initialize_views(&heights);
-    heights
-        .indices()
-        .map(|ij| {
-            let height = heights[ij];
-            fill_views(&mut views, &heights, ij);
-            views
-                .iter()
-                .map(|view| {
-                    cmp::min(
-                        view.len(),
-                        view.iter().take_while(|&&h| h < height).count() + 1,
-                    )
-                })
-                .product()
-        })
-        .max()
-        .unwrap()
+    fn part2(&self, input: &RawInput) -> usize {
+        let heights = Grid::parse_digits(input.as_str());
+        let mut views = initialize_views(&heights);
+        heights
+            .indices()
+            .map(|ij| {
+                let height = heights[ij];
+                fill_views(&mut views, &heights, ij);
+                views
+                    .iter()
+                    .map(|view| {
+                        cmp::min(
+                            view.len(),
+                            view.iter().take_while(|&&h| h < height).count() + 1,
+                        )
+                    })
+                    .product()
+            })
+            .max()
+            .unwrap()
+    }
 }
 
 fn initialize_views(heights: &Grid<u32>) -> [Vec<u32>; 4] {
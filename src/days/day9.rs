@@ -1,13 +1,27 @@
+use crate::harness::day::Day;
 use crate::harness::input::RawInput;
+use crate::unparsed_input;
 use crate::util::coords::Coord2;
 use std::collections::HashSet;
 
-pub fn solve_part1(input: RawInput) -> usize {
-    solve(input, 2)
-}
+pub struct Day9;
+
+impl Day for Day9 {
+    const NUMBER: usize = 9;
+    const TITLE: &'static str = "Rope Bridge";
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    unparsed_input!();
 
-pub fn solve_part2(input: RawInput) -> usize {
-    solve(input, 10)
+    fn part1(&self, input: &RawInput) -> usize {
+        solve(*input, 2)
+    }
+
+    fn part2(&self, input: &RawInput) -> usize {
+        solve(*input, 10)
+    }
 }
 
 fn solve(input: RawInput, length: usize) -> usize {
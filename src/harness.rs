@@ -1,124 +1,571 @@
-use crate::regex;
-use input::RawInput;
-use std::fmt::{Debug, Display};
-use std::panic::RefUnwindSafe;
+use crate::days;
+use answer::{Answer, SolverOutput};
+use answers::Answers;
+use bench::Samples;
+use cli::{Args, Command, Exit, Format};
+use clock::Stopwatch;
+use day::Day;
+use input::{InputText, RawInput};
+use panics::Panic;
+use rayon::prelude::*;
+use report::{PartReport, RecordedAnswer, Solution, SolverError, Status, TestReport};
+use std::panic::AssertUnwindSafe;
 use std::path::Path;
-use std::str::FromStr;
-use std::time::Instant;
-use std::{error, fs};
+use std::time::{Duration, Instant};
+use std::{cmp, error, hint};
+use test_input::TestInput;
 
+pub mod answer;
+pub mod answers;
+pub mod bench;
+mod child;
+pub mod cli;
+mod clock;
+pub mod day;
 pub mod input;
+#[cfg(unix)]
+mod mmap;
+pub mod output;
 mod panics;
+pub mod report;
+mod scaffold;
+pub mod test_input;
+mod watch;
 
-pub fn solve<F1, F2, O1, O2>(day: usize, solve_part1: F1, solve_part2: F2)
-where
-    F1: RefUnwindSafe + Fn(RawInput) -> O1,
-    F2: RefUnwindSafe + Fn(RawInput) -> O2,
-    O1: Display + Eq + FromStr,
-    O2: Display + Eq + FromStr,
-    <O1 as FromStr>::Err: error::Error + 'static,
-    <O2 as FromStr>::Err: error::Error + 'static,
-{
-    let input_filename = format!("input/day{}-input.txt", day);
-    let test_input_filename = format!("input/day{}-test-input.txt", day);
-    let input_text = fs::read_to_string(Path::new(&input_filename)).unwrap();
-    let raw_test_input = fs::read_to_string(Path::new(&test_input_filename)).unwrap();
-    let test_input = TestInput::try_from(raw_test_input.as_str()).unwrap();
-    solve_part(SolvePartArgs {
-        part: 1,
-        solve: solve_part1,
-        input: &input_text,
-        test_input: test_input.text,
-        test_expected_output: test_input.part1_expected,
-    });
-    println!();
-    solve_part(SolvePartArgs {
-        part: 2,
-        solve: solve_part2,
-        input: &input_text,
-        test_input: test_input.text,
-        test_expected_output: test_input.part2_expected,
-    });
+/// A registered `Day`, with its parsed input and output types erased so that
+/// every day can live in the same registry.
+pub struct RegisteredDay {
+    pub day: usize,
+    pub title: &'static str,
+    pub test_inputs: &'static [TestInput<'static>],
+    pub solver: &'static dyn DaySolver,
 }
 
+impl RegisteredDay {
+    pub const fn new<D: Day>(day: &'static D) -> Self {
+        Self {
+            day: D::NUMBER,
+            title: D::TITLE,
+            test_inputs: D::TEST_INPUTS,
+            solver: day,
+        }
+    }
+}
+
+/// Runs a day's parts against its inputs. Implemented for every `Day`.
+pub trait DaySolver: Sync {
+    fn solve(&self, args: DayArgs) -> Vec<PartReport>;
+
+    /// Times `runs` runs of one part against `input`, after a few untimed
+    /// warm-up runs. Returns `None` if the part is not implemented, or the
+    /// error returned by the solver or where and why it panicked if it fails.
+    fn bench(
+        &self,
+        part: usize,
+        input: RawInput,
+        runs: usize,
+    ) -> Result<Option<Samples>, Box<dyn error::Error>>;
+}
+
+/// The inputs to run a day's parts against. Each input is parsed once and
+/// shared by the parts. Test inputs without an expected output for a part are
+/// ignored for that part.
 #[derive(Copy, Clone, Debug)]
-struct SolvePartArgs<'a, F, O> {
-    part: usize,
-    solve: F,
-    input: &'a str,
-    test_input: &'a str,
-    test_expected_output: Option<O>,
-}
-
-fn solve_part<F, O>(
-    SolvePartArgs {
-        part,
-        solve,
-        input,
-        test_input,
-        test_expected_output,
-    }: SolvePartArgs<F, O>,
-) where
-    F: RefUnwindSafe + Fn(RawInput) -> O,
-    O: Display + Eq,
-{
-    if let Some(expected) = test_expected_output {
-        if let Some(test_output) = panics::catching_todo(|| solve(RawInput::new(test_input))) {
-            if test_output == expected {
-                println!("Part {} test output: {} ✅", part, test_output);
-            } else {
-                println!("Part {} test output: {} ❌", part, test_output);
-                println!("          Expected: {}", expected);
-                return;
+pub struct DayArgs<'a> {
+    pub day: usize,
+    pub parts: &'a [usize],
+    pub input: Option<RawInput<'a>>,
+    pub test_inputs: &'a [TestInput<'a>],
+    /// The recorded answer for each part.
+    pub recorded_answers: [Option<&'a str>; 2],
+}
+
+impl<D: Day> DaySolver for D {
+    fn solve(&self, args: DayArgs) -> Vec<PartReport> {
+        let mut reports: Vec<_> = args
+            .parts
+            .iter()
+            .map(|&part| PartReport {
+                title: D::TITLE,
+                ascii_art: D::ASCII_ART[part - 1],
+                not_implemented: part == 2 && !D::PART2_EXISTS,
+                ..PartReport::new(args.day, part)
+            })
+            .collect();
+        for test_input in args.test_inputs {
+            let running: Vec<_> = (0..reports.len())
+                .filter(|&r| {
+                    reports[r].is_running() && test_input.expected(reports[r].part).is_some()
+                })
+                .collect();
+            if running.is_empty() {
+                continue;
             }
-        } else {
-            println!("Part {} not implemented.", part);
-            return;
+            let name = test_input.name;
+            let (parsed, parse_printed) = match parse_input(self, test_input.raw_input()) {
+                ParseResult::Parsed(parsed, _, printed) => (parsed, printed),
+                ParseResult::NotImplemented => {
+                    for r in running {
+                        reports[r].not_implemented = true;
+                    }
+                    continue;
+                }
+                ParseResult::Error(error) => {
+                    for r in running {
+                        reports[r].error = Some(SolverError::new(Some(name), &error));
+                    }
+                    continue;
+                }
+            };
+            for r in running {
+                let report = &mut reports[r];
+                let expected = Answer::parse(test_input.expected(report.part).unwrap());
+                match report.part {
+                    1 => run_test(report, name, &expected, &parse_printed, || {
+                        self.part1(&parsed)
+                    }),
+                    _ => run_test(report, name, &expected, &parse_printed, || {
+                        self.part2(&parsed)
+                    }),
+                }
+            }
+        }
+        let Some(input) = args.input else {
+            return reports;
+        };
+        let running: Vec<_> = (0..reports.len())
+            .filter(|&r| reports[r].is_running() && !reports[r].failed())
+            .collect();
+        if running.is_empty() {
+            return reports;
+        }
+        let (parsed, parse_duration, parse_printed) = match parse_input(self, input) {
+            ParseResult::Parsed(parsed, duration, printed) => {
+                (parsed, D::PARSES_INPUT.then_some(duration), printed)
+            }
+            ParseResult::NotImplemented => {
+                for r in running {
+                    reports[r].not_implemented = true;
+                }
+                return reports;
+            }
+            ParseResult::Error(error) => {
+                for r in running {
+                    reports[r].error = Some(SolverError::new(None, &error));
+                }
+                return reports;
+            }
+        };
+        // Each part is its own task, so that both parts run at once when there
+        // are threads to spare.
+        let (parsed, parse_printed) = (&parsed, parse_printed.as_str());
+        let tasks: Vec<Box<dyn FnOnce() + Send + '_>> = reports
+            .iter_mut()
+            .filter(|report| report.is_running() && !report.failed())
+            .map(|report| {
+                // ASCII art is read by a person, so the value returned is meaningless.
+                let recorded = if report.ascii_art {
+                    None
+                } else {
+                    args.recorded_answers[report.part - 1].map(Answer::parse)
+                };
+                let task: Box<dyn FnOnce() + Send + '_> = match report.part {
+                    1 => Box::new(move || {
+                        run_input(report, recorded, parse_duration, parse_printed, || {
+                            self.part1(parsed)
+                        })
+                    }),
+                    _ => Box::new(move || {
+                        run_input(report, recorded, parse_duration, parse_printed, || {
+                            self.part2(parsed)
+                        })
+                    }),
+                };
+                task
+            })
+            .collect();
+        tasks.into_par_iter().for_each(|task| task());
+        reports
+    }
+
+    fn bench(
+        &self,
+        part: usize,
+        input: RawInput,
+        runs: usize,
+    ) -> Result<Option<Samples>, Box<dyn error::Error>> {
+        if part == 2 && !D::PART2_EXISTS {
+            return Ok(None);
+        }
+        // What the solver prints isn't shown, since it would drown out the
+        // timings, and isn't kept, since it would grow with every run.
+        output::discarding(|| -> Result<_, Box<dyn error::Error>> {
+            let check = panics::catching_panics(AssertUnwindSafe(|| {
+                let parsed = self.parse(input)?;
+                match part {
+                    1 => self.part1(&parsed).into_answer().map(drop),
+                    _ => self.part2(&parsed).into_answer().map(drop),
+                }
+            }));
+            match check {
+                Ok(result) => result?,
+                Err(Panic::Unimplemented) => return Ok(None),
+                Err(panic) => return Err(panic.to_string().into()),
+            }
+            let time_part = |parsed: &D::Parsed<'_>| match part {
+                1 => time(|| self.part1(parsed)),
+                _ => time(|| self.part2(parsed)),
+            };
+            for _ in 1..bench::warmup_runs(runs) {
+                time_part(&self.parse(input)?);
+            }
+            let mut samples = Samples::default();
+            for _ in 0..runs {
+                let start_time = Instant::now();
+                let parsed = hint::black_box(self.parse(input)?);
+                let parse_duration = start_time.elapsed();
+                samples.solve.push(time_part(&parsed));
+                if D::PARSES_INPUT {
+                    samples.parse.push(parse_duration);
+                }
+            }
+            Ok(Some(samples))
+        })
+    }
+}
+
+enum ParseResult<T> {
+    /// The parsed input, how long parsing took, and what it printed.
+    Parsed(T, Duration, String),
+    NotImplemented,
+    Error(Box<dyn error::Error>),
+}
+
+fn parse_input<'a, D: Day>(day: &D, input: RawInput<'a>) -> ParseResult<D::Parsed<'a>> {
+    let result = panics::catching_panics(AssertUnwindSafe(|| {
+        output::capturing(|| {
+            let stopwatch = Stopwatch::start();
+            let parsed = day.parse(input);
+            (parsed, stopwatch.elapsed())
+        })
+    }));
+    match result {
+        Ok(((Ok(parsed), duration), printed)) => ParseResult::Parsed(parsed, duration, printed),
+        Ok(((Err(error), _), _)) => ParseResult::Error(error),
+        Err(Panic::Unimplemented) => ParseResult::NotImplemented,
+        Err(panic) => ParseResult::Error(panic.to_string().into()),
+    }
+}
+
+/// Runs `f` and returns how long it took, excluding dropping its output.
+fn time<O>(f: impl FnOnce() -> O) -> Duration {
+    let start_time = Instant::now();
+    let output = hint::black_box(f());
+    let duration = start_time.elapsed();
+    drop(output);
+    duration
+}
+
+pub fn run(args: &Args) -> Exit {
+    if let Some(Command::NewDay { day, title }) = &args.command {
+        return scaffold::new_day(*day as usize, title.as_deref(), args.input_dir());
+    }
+    if args.list {
+        for registered in days::DAYS {
+            println!("{:>2}  {}", registered.day, registered.title);
+        }
+        return Exit::Success;
+    }
+    let selected: Vec<_> = if args.all {
+        days::DAYS.iter().collect()
+    } else {
+        match selected_day(args) {
+            Ok(registered) => vec![registered],
+            Err(exit) => return exit,
+        }
+    };
+    if let Some(runs) = args.bench {
+        return bench::run(args, &selected, runs as usize);
+    }
+    if args.has_limits() || args.watch {
+        if let Err(exit) = child::check_args(args) {
+            return exit;
         }
     }
-    let result = panics::catching_todo(|| {
-        let start_time = Instant::now();
-        let output = solve(RawInput::new(input));
-        let duration = start_time.elapsed();
-        (output, duration)
+    if args.watch {
+        return watch::run(selected[0], args);
+    }
+    // Keep anything printed while solving, other than what `output` captures,
+    // out of a machine-readable report.
+    let redirect = match args.format {
+        Format::Text => None,
+        Format::Json | Format::Junit => match output::StdoutToStderr::start() {
+            Ok(redirect) => Some(redirect),
+            Err(error) => {
+                eprintln!("Could not redirect stdout: {}", error);
+                return Exit::Usage;
+            }
+        },
+    };
+    let start_time = Instant::now();
+    let mut answers = match load_answers() {
+        Ok(answers) => answers,
+        Err(exit) => return exit,
+    };
+    let pool = match rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.unwrap_or(0) as usize)
+        .build()
+    {
+        Ok(pool) => pool,
+        Err(error) => {
+            eprintln!("Could not start the thread pool: {}", error);
+            return Exit::Usage;
+        }
+    };
+    // `collect` keeps the days in order, however they finish.
+    let results: Vec<_> = pool.install(|| {
+        selected
+            .par_iter()
+            .map(|registered| {
+                if args.has_limits() {
+                    child::solve(registered, args)
+                } else {
+                    solve(registered, args, &answers)
+                }
+            })
+            .collect()
     });
-    if let Some((output, duration)) = result {
-        println!("Part {} output: {}", part, output);
-        println!("   ↑ Duration: {:.2?}", duration);
+    let mut reports = vec![];
+    let mut exit = Exit::Success;
+    for result in results {
+        match result {
+            Ok(day_reports) => reports.extend(day_reports),
+            Err(day_exit) => exit = cmp::max(exit, day_exit),
+        }
+    }
+    if let Some(path) = &args.report_to {
+        return cmp::max(exit, child::write_reports(path, &reports));
+    }
+    drop(redirect);
+    report::print(&reports, args, start_time.elapsed());
+    exit = cmp::max(exit, exit_for(&reports));
+    cmp::max(exit, record(args, &mut answers, &reports))
+}
+
+fn selected_day(args: &Args) -> Result<&'static RegisteredDay, Exit> {
+    match args.day {
+        Some(day) => days::get(day).ok_or_else(|| {
+            eprintln!("No solution registered for day {}.", day);
+            Exit::Usage
+        }),
+        None => Ok(days::latest()),
+    }
+}
+
+fn load_answers() -> Result<Answers, Exit> {
+    Answers::load(Path::new(answers::DEFAULT_PATH)).map_err(|error| {
+        eprintln!("Could not load {}: {}", answers::DEFAULT_PATH, error);
+        Exit::BadInput
+    })
+}
+
+/// Saves the answers of parts that were solved, whose tests (if any) passed,
+/// and that have nothing recorded yet, if `--record` was passed.
+fn record(args: &Args, answers: &mut Answers, reports: &[PartReport]) -> Exit {
+    if !args.record {
+        return Exit::Success;
+    }
+    let mut count = 0;
+    for report in reports {
+        // ASCII art is read by a person, so the value returned is meaningless.
+        if report.failed() || report.ascii_art {
+            continue;
+        }
+        if let Some(solution) = &report.solution {
+            if answers.insert(report.day, report.part, solution.answer.clone()) {
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        return Exit::Success;
+    }
+    match answers.save() {
+        Ok(()) => {
+            let message = format!(
+                "Recorded {} new answer(s) in {}.",
+                count,
+                answers.path().display()
+            );
+            // Keep machine-readable output parseable.
+            if args.format == Format::Text {
+                println!("{}", message);
+            } else {
+                eprintln!("{}", message);
+            }
+            Exit::Success
+        }
+        Err(error) => {
+            eprintln!("Could not save {}: {}", answers.path().display(), error);
+            Exit::BadInput
+        }
+    }
+}
+
+fn exit_for(reports: &[PartReport]) -> Exit {
+    let statuses: Vec<_> = reports.iter().map(PartReport::status).collect();
+    if statuses.contains(&Status::Errored) {
+        Exit::BadInput
+    } else if statuses.contains(&Status::Failed) || statuses.contains(&Status::TimedOut) {
+        Exit::TestFailed
     } else {
-        println!("Part {} not implemented.", part);
+        Exit::Success
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-struct TestInput<'a, O1, O2> {
-    part1_expected: Option<O1>,
-    part2_expected: Option<O2>,
-    text: &'a str,
-}
-
-// Not implementing `FromStr` because of the lifetime bound.
-impl<'a, O1, O2> TryFrom<&'a str> for TestInput<'a, O1, O2>
-where
-    O1: FromStr,
-    O2: FromStr,
-    <O1 as FromStr>::Err: error::Error + 'static,
-    <O2 as FromStr>::Err: error::Error + 'static,
-{
-    type Error = Box<dyn error::Error>;
-
-    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
-        let re = regex!(
-            r"(?s)^Part 1 expected: *([^\n]+)?
-Part 2 expected: *([^\n]+)?
- *
-(.*)$"
-        );
-        let caps = re.captures(s).ok_or("Invalid test input format.")?;
-        Ok(TestInput {
-            part1_expected: caps.get(1).map(|m| m.as_str().parse()).transpose()?,
-            part2_expected: caps.get(2).map(|m| m.as_str().parse()).transpose()?,
-            text: caps.get(3).unwrap().as_str(),
+/// Runs the parts of a single day selected by `args`. Problems with the input
+/// files are printed immediately and returned as an `Exit`.
+fn solve(
+    registered: &RegisteredDay,
+    args: &Args,
+    answers: &Answers,
+) -> Result<Vec<PartReport>, Exit> {
+    let day = registered.day;
+    let input_text = if args.test_only {
+        None
+    } else {
+        match read_input(args, day) {
+            Ok(text) => Some(text),
+            Err(error) => {
+                eprintln!("Could not read input for day {}: {}", day, error);
+                return Err(Exit::BadInput);
+            }
+        }
+    };
+    let test_files = if args.skip_test {
+        vec![]
+    } else {
+        match test_input::read_test_files(args.input_dir(), day) {
+            Ok(files) => files,
+            Err(error) => {
+                eprintln!("Could not read test inputs for day {}: {}", day, error);
+                return Err(Exit::BadInput);
+            }
+        }
+    };
+    let test_inputs: Result<Vec<_>, _> = test_files.iter().map(TestInput::parse).collect();
+    let test_inputs = match test_inputs {
+        Ok(mut test_inputs) => {
+            if !args.skip_test {
+                test_inputs.extend(registered.test_inputs);
+            }
+            test_inputs
+        }
+        Err(error) => {
+            eprintln!("Invalid test input for day {}: {}", day, error);
+            return Err(Exit::BadInput);
+        }
+    };
+    let parts: Vec<_> = [1, 2]
+        .into_iter()
+        .filter(|&part| args.runs_part(part))
+        .collect();
+    let day_args = DayArgs {
+        day,
+        parts: &parts,
+        input: input_text.as_ref().map(InputText::raw_input),
+        test_inputs: &test_inputs,
+        recorded_answers: if args.checks_recorded_answers() {
+            [answers.get(day, 1), answers.get(day, 2)]
+        } else {
+            [None, None]
+        },
+    };
+    Ok(registered.solver.solve(day_args))
+}
+
+pub(crate) fn read_input(args: &Args, day: usize) -> Result<InputText, String> {
+    if args.reads_stdin() {
+        InputText::read_stdin().map_err(|error| format!("stdin: {}", error))
+    } else {
+        let path = args.input_path(day);
+        InputText::read_file(&path).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+}
+
+/// Runs one part against a parsed test input and adds the result to `report`.
+/// `parse_printed` is what parsing the test input printed.
+fn run_test<O: SolverOutput>(
+    report: &mut PartReport,
+    name: &str,
+    expected: &Answer,
+    parse_printed: &str,
+    solve: impl FnOnce() -> O,
+) {
+    let result = panics::catching_panics(AssertUnwindSafe(|| output::capturing(solve)));
+    let (output, printed) = match result {
+        Ok(result) => result,
+        Err(Panic::Unimplemented) => {
+            report.not_implemented = true;
+            return;
+        }
+        Err(panic) => {
+            report.error = Some(SolverError::new(Some(name), panic));
+            return;
+        }
+    };
+    match output.into_answer() {
+        Ok(output) => report.tests.push(TestReport {
+            name: name.to_owned(),
+            output: output.to_string(),
+            expected: expected.to_string(),
+            passed: output == *expected,
+            printed: parse_printed.to_owned() + &printed,
+        }),
+        Err(error) => report.error = Some(SolverError::new(Some(name), error)),
+    }
+}
+
+/// Runs one part against the parsed puzzle input and records its solution in
+/// `report`. `parse_printed` is what parsing the input printed.
+fn run_input<O: SolverOutput>(
+    report: &mut PartReport,
+    recorded_answer: Option<Answer>,
+    parse_duration: Option<Duration>,
+    parse_printed: &str,
+    solve: impl FnOnce() -> O,
+) {
+    let result = panics::catching_panics(AssertUnwindSafe(|| {
+        output::capturing(|| {
+            let stopwatch = Stopwatch::start();
+            let output = solve();
+            (output, stopwatch.elapsed())
         })
+    }));
+    let ((output, duration), printed) = match result {
+        Ok(result) => result,
+        Err(Panic::Unimplemented) => {
+            report.not_implemented = true;
+            return;
+        }
+        Err(panic) => {
+            report.error = Some(SolverError::new(None, panic));
+            return;
+        }
+    };
+    match output.into_answer() {
+        Ok(output) => {
+            report.solution = Some(Solution {
+                answer: output.to_string(),
+                duration,
+                parse_duration,
+                printed: parse_printed.to_owned() + &printed,
+                recorded: recorded_answer.map(|recorded| RecordedAnswer {
+                    matches: output == recorded,
+                    answer: recorded.to_string(),
+                }),
+            })
+        }
+        Err(error) => report.error = Some(SolverError::new(None, error)),
     }
 }
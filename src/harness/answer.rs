@@ -0,0 +1,173 @@
+use std::collections::{BTreeSet, HashSet};
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::hash::BuildHasher;
+
+/// An answer, either returned by a solver or written down as an expected
+/// output or recorded answer. Answers compare by meaning rather than by text:
+/// `0010` equals `10` whatever integer type the solver returns, surrounding
+/// whitespace is ignored, and the elements of a set may be in any order.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Answer {
+    Integer(i128),
+    /// A single line of text, without surrounding whitespace.
+    Text(String),
+    /// Several lines of text, such as letters drawn in pixels, without
+    /// trailing whitespace or blank lines at either end.
+    Lines(Vec<String>),
+    /// An unordered set, written as `{a, b, c}`. Each element is normalized as
+    /// if it were an answer itself, so it can't contain a comma.
+    Set(BTreeSet<String>),
+}
+
+impl Answer {
+    /// Reads an answer from text. Never fails: anything that isn't an
+    /// integer, a set or several lines is text.
+    pub fn parse(text: &str) -> Self {
+        let lines: Vec<_> = text.lines().map(str::trim_end).collect();
+        let start = lines.iter().position(|line| !line.is_empty());
+        let end = lines.iter().rposition(|line| !line.is_empty());
+        let (Some(start), Some(end)) = (start, end) else {
+            return Answer::Text(String::new());
+        };
+        if start < end {
+            return Answer::Lines(
+                lines[start..=end]
+                    .iter()
+                    .map(|&line| line.to_owned())
+                    .collect(),
+            );
+        }
+        let line = lines[start].trim();
+        if let Some(elements) = line.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            return Answer::set(
+                elements
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|element| !element.is_empty()),
+            );
+        }
+        let is_integer = line
+            .strip_prefix(['+', '-'])
+            .unwrap_or(line)
+            .bytes()
+            .all(|b| b.is_ascii_digit());
+        match line.parse() {
+            Ok(n) if is_integer => Answer::Integer(n),
+            _ => Answer::Text(line.to_owned()),
+        }
+    }
+
+    /// A set of the given elements.
+    pub fn set<T: Display>(elements: impl IntoIterator<Item = T>) -> Self {
+        Answer::Set(
+            elements
+                .into_iter()
+                .map(|element| Answer::parse(&element.to_string()).to_string())
+                .collect(),
+        )
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+            Answer::Set(elements) => {
+                let elements: Vec<_> = elements.iter().map(String::as_str).collect();
+                write!(f, "{{{}}}", elements.join(", "))
+            }
+        }
+    }
+}
+
+/// A value returned by a solver. Either the answer itself, or a `Result` whose
+/// error is reported in place of an answer.
+pub trait SolverOutput {
+    fn into_answer(self) -> Result<Answer, Box<dyn error::Error>>;
+}
+
+macro_rules! impl_solver_output_for_integers {
+    ($($T:ty),*) => {
+        $(
+            impl SolverOutput for $T {
+                fn into_answer(self) -> Result<Answer, Box<dyn error::Error>> {
+                    match i128::try_from(self) {
+                        Ok(n) => Ok(Answer::Integer(n)),
+                        Err(_) => Err(format!("{} is too large to compare as an answer.", self).into()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_solver_output_for_integers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl SolverOutput for Answer {
+    fn into_answer(self) -> Result<Answer, Box<dyn error::Error>> {
+        Ok(self)
+    }
+}
+
+impl SolverOutput for String {
+    fn into_answer(self) -> Result<Answer, Box<dyn error::Error>> {
+        Ok(Answer::parse(&self))
+    }
+}
+
+impl<T: Display> SolverOutput for BTreeSet<T> {
+    fn into_answer(self) -> Result<Answer, Box<dyn error::Error>> {
+        Ok(Answer::set(self))
+    }
+}
+
+impl<T: Display, S: BuildHasher> SolverOutput for HashSet<T, S> {
+    fn into_answer(self) -> Result<Answer, Box<dyn error::Error>> {
+        Ok(Answer::set(self))
+    }
+}
+
+impl<T, E> SolverOutput for Result<T, E>
+where
+    T: SolverOutput,
+    E: Into<Box<dyn error::Error>>,
+{
+    fn into_answer(self) -> Result<Answer, Box<dyn error::Error>> {
+        self.map_err(Into::into)?.into_answer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Answer::parse("0010"), Answer::Integer(10));
+        assert_eq!(Answer::parse(" +10\n"), Answer::Integer(10));
+        assert_eq!(Answer::parse("-7"), Answer::Integer(-7));
+        assert_eq!(Answer::parse("1e3"), Answer::Text("1e3".to_owned()));
+        assert_eq!(Answer::parse(" CMZ "), Answer::Text("CMZ".to_owned()));
+        assert_eq!(
+            Answer::parse("\n#.# \n.#.\n\n"),
+            Answer::Lines(vec!["#.#".to_owned(), ".#.".to_owned()])
+        );
+        assert_eq!(Answer::parse("{b, 02,a}"), Answer::set(["a", "b", "2"]));
+        assert_eq!(Answer::parse("{}"), Answer::Set(BTreeSet::new()));
+    }
+
+    #[test]
+    fn test_compare_solver_outputs() {
+        assert_eq!(10u8.into_answer().unwrap(), Answer::parse("0010"));
+        assert_eq!((-3i64).into_answer().unwrap(), Answer::parse("-3"));
+        assert!(u128::MAX.into_answer().is_err());
+        assert_eq!("42".to_owned().into_answer().unwrap(), Answer::Integer(42));
+        let set: HashSet<_> = [3, 1, 2].into_iter().collect();
+        let answer = set.into_answer().unwrap();
+        assert_eq!(answer, Answer::parse("{2, 1, 3}"));
+        assert_eq!(answer.to_string(), "{1, 2, 3}");
+    }
+}
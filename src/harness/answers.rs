@@ -0,0 +1,71 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{error, fs};
+
+pub const DEFAULT_PATH: &str = "input/answers.json";
+
+/// Accepted answers for the real puzzle inputs, keyed by day and part. Stored
+/// as a JSON array of `{"day", "part", "answer"}` objects sorted by day and
+/// part, so that diffs stay readable.
+#[derive(Clone, Debug)]
+pub struct Answers {
+    path: PathBuf,
+    entries: BTreeMap<(usize, usize), String>,
+}
+
+impl Answers {
+    /// Loads answers from `path`. A missing file is treated as empty.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn error::Error>> {
+        let mut entries = BTreeMap::new();
+        if path.exists() {
+            let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+            for entry in value.as_array().ok_or("Expected an array of answers.")? {
+                let day = entry["day"].as_u64().ok_or("Missing day.")? as usize;
+                let part = entry["part"].as_u64().ok_or("Missing part.")? as usize;
+                let answer = entry["answer"].as_str().ok_or("Missing answer.")?;
+                entries.insert((day, part), answer.to_owned());
+            }
+        }
+        Ok(Self {
+            path: path.to_owned(),
+            entries,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, day: usize, part: usize) -> Option<&str> {
+        self.entries.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Records an answer unless one is already recorded for this part. Returns
+    /// whether the answer was added.
+    pub fn insert(&mut self, day: usize, part: usize, answer: String) -> bool {
+        if self.entries.contains_key(&(day, part)) {
+            return false;
+        }
+        self.entries.insert((day, part), answer);
+        true
+    }
+
+    /// Writes one answer per line, which `serde_json`'s pretty printer won't do.
+    pub fn save(&self) -> Result<(), Box<dyn error::Error>> {
+        let lines: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(&(day, part), answer)| {
+                format!(
+                    r#"  {{"day": {}, "part": {}, "answer": {}}}"#,
+                    day,
+                    part,
+                    Value::from(answer.as_str())
+                )
+            })
+            .collect();
+        fs::write(&self.path, format!("[\n{}\n]\n", lines.join(",\n")))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use crate::harness::cli::{Args, Exit};
+use crate::harness::{read_input, RegisteredDay};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+use std::{cmp, error, fs};
+
+/// Timings from repeated runs of one part. `parse` is empty unless the day
+/// parses its input separately from solving it.
+#[derive(Clone, Debug, Default)]
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub solve: Vec<Duration>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        // Nearest-rank percentile.
+        let p95_rank = (n * 95).div_ceil(100);
+        Some(Self {
+            min: sorted[0],
+            median,
+            p95: sorted[p95_rank - 1],
+        })
+    }
+}
+
+/// The number of untimed runs before measuring, to warm up caches and let the
+/// CPU settle on a clock speed.
+pub fn warmup_runs(runs: usize) -> usize {
+    cmp::max(1, runs / 10)
+}
+
+#[derive(Clone, Debug)]
+struct BenchResult {
+    day: usize,
+    part: usize,
+    parse: Option<Stats>,
+    solve: Stats,
+}
+
+/// Benchmarks each part of the given days against its puzzle input, then
+/// optionally compares against and saves a baseline.
+pub fn run(args: &Args, days: &[&RegisteredDay], runs: usize) -> Exit {
+    let baseline = match &args.baseline {
+        Some(path) => match load_baseline(path) {
+            Ok(baseline) => Some(baseline),
+            Err(error) => {
+                eprintln!("Could not load baseline {}: {}", path.display(), error);
+                return Exit::BadInput;
+            }
+        },
+        None => None,
+    };
+    let mut results = vec![];
+    let mut exit = Exit::Success;
+    for registered in days {
+        let day = registered.day;
+        let input = match read_input(args, day) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("Could not read input for day {}: {}", day, error);
+                return Exit::BadInput;
+            }
+        };
+        for part in [1, 2] {
+            if !args.runs_part(part) {
+                continue;
+            }
+            let samples = match registered.solver.bench(part, input.raw_input(), runs) {
+                Ok(Some(samples)) => samples,
+                Ok(None) => continue,
+                Err(error) => {
+                    eprintln!("Day {} part {} returned an error: {}", day, part, error);
+                    exit = Exit::BadInput;
+                    continue;
+                }
+            };
+            results.push(BenchResult {
+                day,
+                part,
+                parse: Stats::from_samples(&samples.parse),
+                solve: Stats::from_samples(&samples.solve).unwrap(),
+            });
+        }
+    }
+    print_results(&results, baseline.as_ref());
+    if let Some(path) = &args.save_baseline {
+        if let Err(error) = save_baseline(path, &results) {
+            eprintln!("Could not save baseline {}: {}", path.display(), error);
+            return Exit::BadInput;
+        }
+        println!("Saved baseline to {}.", path.display());
+    }
+    exit
+}
+
+fn print_results(results: &[BenchResult], baseline: Option<&Baseline>) {
+    println!(
+        "{:<4} {:<4} {:>10} {:>10} {:>10} {:>12} {:>9}",
+        "Day", "Part", "Min", "Median", "P95", "Parse (med)", "Change"
+    );
+    for result in results {
+        let parse = match result.parse {
+            Some(stats) => format!("{:.2?}", stats.median),
+            None => "-".to_owned(),
+        };
+        let change = match baseline.and_then(|b| b.get(&(result.day, result.part))) {
+            Some(&old) => format_change(old, result.solve.median),
+            None => "-".to_owned(),
+        };
+        println!(
+            "{:<4} {:<4} {:>10} {:>10} {:>10} {:>12} {:>9}",
+            result.day,
+            result.part,
+            format!("{:.2?}", result.solve.min),
+            format!("{:.2?}", result.solve.median),
+            format!("{:.2?}", result.solve.p95),
+            parse,
+            change
+        );
+    }
+}
+
+fn format_change(old: Duration, new: Duration) -> String {
+    let old = old.as_secs_f64();
+    if old == 0.0 {
+        return "-".to_owned();
+    }
+    format!("{:+.1}%", (new.as_secs_f64() - old) / old * 100.0)
+}
+
+/// Median solve times from a previous run, keyed by day and part.
+type Baseline = BTreeMap<(usize, usize), Duration>;
+
+fn load_baseline(path: &Path) -> Result<Baseline, Box<dyn error::Error>> {
+    let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let mut baseline = Baseline::new();
+    for entry in value.as_array().ok_or("Expected an array of timings.")? {
+        let day = entry["day"].as_u64().ok_or("Missing day.")? as usize;
+        let part = entry["part"].as_u64().ok_or("Missing part.")? as usize;
+        let median = entry["median_ns"].as_u64().ok_or("Missing median_ns.")?;
+        baseline.insert((day, part), Duration::from_nanos(median));
+    }
+    Ok(baseline)
+}
+
+fn save_baseline(path: &Path, results: &[BenchResult]) -> Result<(), Box<dyn error::Error>> {
+    let lines: Vec<_> = results
+        .iter()
+        .map(|result| {
+            format!(
+                r#"  {{"day": {}, "part": {}, "min_ns": {}, "median_ns": {}, "p95_ns": {}}}"#,
+                result.day,
+                result.part,
+                result.solve.min.as_nanos(),
+                result.solve.median.as_nanos(),
+                result.solve.p95.as_nanos()
+            )
+        })
+        .collect();
+    fs::write(path, format!("[\n{}\n]\n", lines.join(",\n")))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<_> = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_format_change() {
+        let old = Duration::from_millis(200);
+        assert_eq!(format_change(old, Duration::from_millis(150)), "-25.0%");
+        assert_eq!(format_change(old, Duration::from_millis(250)), "+25.0%");
+    }
+}
@@ -0,0 +1,192 @@
+//! Runs parts in child processes running this same binary. This is how
+//! `--timeout` and `--memory-limit` are enforced, since a thread can't be
+//! stopped from outside, and how `--watch` picks up rebuilt code.
+
+use crate::harness::cli::{Args, Exit};
+use crate::harness::report::{self, LimitExceeded, PartReport, SolverError};
+use crate::harness::RegisteredDay;
+use once_cell::sync::OnceCell;
+use rayon::prelude::*;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+use std::{env, fs, io, process, thread};
+
+/// How often to check whether a child process has finished, and how much
+/// memory it is using.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Checks that the parts selected by `args` can run in a child process, and
+/// that its limits can be enforced.
+pub fn check_args(args: &Args) -> Result<(), Exit> {
+    if args.reads_stdin() {
+        eprintln!("--timeout, --memory-limit and --watch can't be used with input from stdin.");
+        return Err(Exit::Usage);
+    }
+    if cfg!(not(target_os = "linux")) && args.memory_limit.is_some() {
+        eprintln!("--memory-limit is only supported on Linux.");
+        return Err(Exit::Usage);
+    }
+    Ok(())
+}
+
+/// Runs the parts of a single day selected by `args`, each in its own process.
+pub fn solve(registered: &RegisteredDay, args: &Args) -> Result<Vec<PartReport>, Exit> {
+    let parts: Vec<_> = [1, 2]
+        .into_iter()
+        .filter(|&part| args.runs_part(part))
+        .collect();
+    let results: Vec<_> = parts
+        .par_iter()
+        .map(|&part| solve_part(registered, part, args))
+        .collect();
+    results.into_iter().collect()
+}
+
+fn solve_part(registered: &RegisteredDay, part: usize, args: &Args) -> Result<PartReport, Exit> {
+    let day = registered.day;
+    let report_path = env::temp_dir().join(format!(
+        "advent-of-code-2022-{}-day{}-part{}.json",
+        process::id(),
+        day,
+        part
+    ));
+    let status = match run_child(day, part, args, &report_path) {
+        Ok(Ended::Exited(status)) => status,
+        Ok(Ended::Stopped(limit)) => {
+            let mut report = PartReport::new(day, part);
+            report.title = registered.title;
+            report.limit_exceeded = Some(limit);
+            return Ok(report);
+        }
+        Err(error) => {
+            eprintln!("Could not run day {} part {}: {}", day, part, error);
+            return Err(Exit::Usage);
+        }
+    };
+    let written = fs::read_to_string(&report_path);
+    let _ = fs::remove_file(&report_path);
+    match written {
+        Ok(written) => {
+            let value: Value = serde_json::from_str(&written).unwrap_or_default();
+            // An empty report means the child couldn't read its input, and has
+            // already said why.
+            match value.as_array().and_then(|reports| reports.first()) {
+                Some(value) => report::from_json(value, registered.title).ok_or_else(|| {
+                    eprintln!("Invalid report for day {} part {}.", day, part);
+                    Exit::BadInput
+                }),
+                None => Err(Exit::BadInput),
+            }
+        }
+        Err(_) => {
+            let mut report = PartReport::new(day, part);
+            report.title = registered.title;
+            report.error = Some(SolverError {
+                test_input: None,
+                message: format!("Crashed ({})", status),
+            });
+            Ok(report)
+        }
+    }
+}
+
+/// How a child process ended.
+enum Ended {
+    Exited(ExitStatus),
+    /// Killed for exceeding a limit.
+    Stopped(LimitExceeded),
+}
+
+/// Runs one part in a child process, which writes its report to
+/// `report_path`.
+fn run_child(day: usize, part: usize, args: &Args, report_path: &Path) -> io::Result<Ended> {
+    let mut command = Command::new(current_exe()?);
+    command
+        .args(["--day", &day.to_string(), "--part", &part.to_string()])
+        .args(["--jobs", "1", "--report-to"])
+        .arg(report_path)
+        .stdin(Stdio::null());
+    if let Some(input) = &args.input {
+        command.arg("--input").arg(input);
+    }
+    if args.test_only {
+        command.arg("--test-only");
+    }
+    if args.skip_test {
+        command.arg("--skip-test");
+    }
+    let child = command.spawn()?;
+    wait(child, args.timeout, args.memory_limit)
+}
+
+/// The path to this binary. Read once, since on some platforms the path stops
+/// resolving once `--watch` rebuilds the binary.
+fn current_exe() -> io::Result<&'static Path> {
+    static PATH: OnceCell<PathBuf> = OnceCell::new();
+    PATH.get_or_try_init(env::current_exe).map(PathBuf::as_path)
+}
+
+/// Waits for `child` to finish, or kills it once `timeout` has passed or its
+/// resident memory reaches `memory_limit` megabytes.
+fn wait(
+    mut child: Child,
+    timeout: Option<Duration>,
+    memory_limit: Option<u64>,
+) -> io::Result<Ended> {
+    if timeout.is_none() && memory_limit.is_none() {
+        return child.wait().map(Ended::Exited);
+    }
+    let start_time = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Ended::Exited(status));
+        }
+        let exceeded = match (timeout, memory_limit) {
+            (Some(timeout), _) if start_time.elapsed() >= timeout => {
+                Some(LimitExceeded::Time(timeout))
+            }
+            (_, Some(megabytes))
+                if resident_size(child.id()).is_some_and(|bytes| bytes >> 20 >= megabytes) =>
+            {
+                Some(LimitExceeded::Memory(megabytes))
+            }
+            _ => None,
+        };
+        if let Some(exceeded) = exceeded {
+            child.kill()?;
+            child.wait()?;
+            return Ok(Ended::Stopped(exceeded));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// How many bytes of a process are in memory, as opposed to reserved or
+/// swapped out. `None` if the process has already gone.
+#[cfg(target_os = "linux")]
+fn resident_size(pid: u32) -> Option<u64> {
+    let statm = fs::read_to_string(format!("/proc/{}/statm", pid)).ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // SAFETY: `sysconf` has no preconditions.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Some(pages * page_size as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_size(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Writes reports for a parent process to read with `report::from_json`.
+pub fn write_reports(path: &Path, reports: &[PartReport]) -> Exit {
+    let records: Vec<_> = reports.iter().map(report::to_json).collect();
+    match fs::write(path, serde_json::to_string(&records).unwrap()) {
+        Ok(()) => Exit::Success,
+        Err(error) => {
+            eprintln!("Could not write {}: {}", path.display(), error);
+            Exit::BadInput
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Debug, Parser)]
+#[command(
+    about = "Runs solutions to Advent of Code 2022.",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Day to run. Defaults to the most recent day.
+    #[arg(short, long, conflicts_with = "all")]
+    pub day: Option<usize>,
+
+    /// Only run this part.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Read the puzzle input from this file instead of `input/dayN-input.txt`.
+    /// Pass `-` to read from stdin.
+    #[arg(short, long, conflicts_with = "all")]
+    pub input: Option<PathBuf>,
+
+    /// Only run against the test input.
+    #[arg(long, conflicts_with = "skip_test")]
+    pub test_only: bool,
+
+    /// Run against the puzzle input without checking the test input first.
+    #[arg(long)]
+    pub skip_test: bool,
+
+    /// Run every day.
+    #[arg(long)]
+    pub all: bool,
+
+    /// Run up to this many days and parts at once. Defaults to the number of
+    /// CPUs. Times are per-thread CPU time, so they are comparable whatever
+    /// the value.
+    #[arg(
+        short,
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with = "bench"
+    )]
+    pub jobs: Option<u32>,
+
+    /// Stop each part after this many seconds, including its tests, and report
+    /// it as a TIMEOUT. Each part then runs in its own process.
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = parse_seconds,
+        conflicts_with = "bench"
+    )]
+    pub timeout: Option<Duration>,
+
+    /// Stop each part when its resident memory, as reported by
+    /// `/proc/<pid>/statm` and checked every millisecond, reaches this many
+    /// megabytes, and report it as a TIMEOUT. Each part then runs in its own
+    /// process. Only supported on Linux.
+    #[arg(
+        long,
+        value_name = "MB",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "bench"
+    )]
+    pub memory_limit: Option<u64>,
+
+    /// Rerun the day whenever its input files or the code change, rebuilding
+    /// first if the code changed, and show how the answers changed.
+    #[arg(long, conflicts_with_all = ["all", "record", "bench"])]
+    pub watch: bool,
+
+    /// Write a JSON report to this file instead of printing the results. Used
+    /// to collect the results of parts run in their own process.
+    #[arg(long, value_name = "PATH", hide = true)]
+    pub report_to: Option<PathBuf>,
+
+    /// List the registered days and their titles instead of running anything.
+    #[arg(long, exclusive = true)]
+    pub list: bool,
+
+    /// How to print results. Benchmarks are only printed as text.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = Format::Text,
+        conflicts_with = "bench"
+    )]
+    pub format: Format,
+
+    /// Save answers to `input/answers.json` for parts that have no recorded
+    /// answer yet. Answers of parts whose tests failed are not recorded.
+    #[arg(long, conflicts_with_all = ["input", "test_only"])]
+    pub record: bool,
+
+    /// Instead of checking answers, time this many runs of each part against
+    /// the puzzle input and report the min, median and 95th percentile.
+    #[arg(
+        long,
+        value_name = "RUNS",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with_all = ["test_only", "record"]
+    )]
+    pub bench: Option<u32>,
+
+    /// Compare benchmark medians against a baseline saved by `--save-baseline`.
+    #[arg(long, value_name = "PATH", requires = "bench")]
+    pub baseline: Option<PathBuf>,
+
+    /// Save benchmark timings as a baseline for later runs.
+    #[arg(long, value_name = "PATH", requires = "bench")]
+    pub save_baseline: Option<PathBuf>,
+}
+
+impl Args {
+    pub fn runs_part(&self, part: usize) -> bool {
+        self.part.is_none_or(|p| p as usize == part)
+    }
+
+    pub fn input_path(&self, day: usize) -> PathBuf {
+        self.input
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("input/day{}-input.txt", day)))
+    }
+
+    /// The directory containing the test inputs and recorded answers.
+    pub fn input_dir(&self) -> &Path {
+        Path::new("input")
+    }
+
+    /// Whether each part has to run in its own process so that it can be
+    /// stopped.
+    pub fn has_limits(&self) -> bool {
+        self.timeout.is_some() || self.memory_limit.is_some()
+    }
+
+    /// Recorded answers only apply to the real puzzle inputs.
+    pub fn checks_recorded_answers(&self) -> bool {
+        self.input.is_none() && !self.test_only
+    }
+
+    pub fn reads_stdin(&self) -> bool {
+        self.input
+            .as_deref()
+            .is_some_and(|path| path.as_os_str() == "-")
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create the module and input files for a new day, and register it.
+    /// Refuses to overwrite existing files.
+    NewDay {
+        /// Day of the month.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// The puzzle's title.
+        title: Option<String>,
+    },
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = s.parse().map_err(|error| format!("{}", error))?;
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(duration) if !duration.is_zero() => Ok(duration),
+        _ => Err("must be a positive number of seconds".to_owned()),
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Human-readable output. A table when running every day.
+    Text,
+    /// A JSON array with one object per day and part.
+    Json,
+    /// A JUnit XML report with one test case per day and part.
+    Junit,
+}
+
+/// How a run ended, from best to worst. When several days are run, the worst
+/// outcome determines the exit code.
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Exit {
+    /// Everything ran, and every test that ran passed.
+    Success = 0,
+    /// A test output did not match its expected value, or an answer did not
+    /// match its recorded value.
+    TestFailed = 1,
+    /// The arguments were invalid. Also used by `clap` for parse errors.
+    Usage = 2,
+    /// An input file could not be read or parsed, or a solver returned an
+    /// error or panicked.
+    BadInput = 3,
+}
+
+impl From<Exit> for ExitCode {
+    fn from(exit: Exit) -> Self {
+        ExitCode::from(exit as u8)
+    }
+}
@@ -0,0 +1,74 @@
+//! Timing for tasks that may run in parallel. Wall-clock time would include
+//! time a thread spends waiting for a core while other tasks run, so where the
+//! platform supports it the CPU time used by the current thread is measured
+//! instead.
+
+use std::time::Duration;
+#[cfg(not(unix))]
+use std::time::Instant;
+
+/// Measures the time the current thread spends running, from when it was
+/// started. Must be read on the thread that started it.
+pub struct Stopwatch {
+    #[cfg(unix)]
+    start: Duration,
+    #[cfg(not(unix))]
+    start: Instant,
+}
+
+impl Stopwatch {
+    #[cfg(unix)]
+    pub fn start() -> Self {
+        Self {
+            start: thread_cpu_time(),
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn start() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn elapsed(&self) -> Duration {
+        thread_cpu_time().saturating_sub(self.start)
+    }
+
+    #[cfg(not(unix))]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+#[cfg(unix)]
+fn thread_cpu_time() -> Duration {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `time` is a valid `timespec` to write to.
+    let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    assert_eq!(result, 0, "Could not read the thread CPU clock.");
+    Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint;
+
+    #[test]
+    fn test_stopwatch_ignores_sleep() {
+        let stopwatch = Stopwatch::start();
+        let sum: u64 = (0..1_000_000u64).map(hint::black_box).sum();
+        hint::black_box(sum);
+        let busy = stopwatch.elapsed();
+        assert!(busy > Duration::ZERO);
+        if cfg!(unix) {
+            std::thread::sleep(Duration::from_millis(50));
+            assert!(stopwatch.elapsed() - busy < Duration::from_millis(25));
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::harness::answer::SolverOutput;
+use crate::harness::input::RawInput;
+use crate::harness::test_input::TestInput;
+use std::error;
+use std::marker::PhantomData;
+
+/// A day's solution, split into a parse phase that runs once per input and two
+/// parts that borrow its result.
+pub trait Day: Sync {
+    /// The puzzle's day of the month, from 1 to 25.
+    const NUMBER: usize;
+    /// The puzzle's title, as shown on the Advent of Code site.
+    const TITLE: &'static str;
+    /// Whether each part's answer is drawn as ASCII art, which has to be read
+    /// by a person. The value returned for such a part is not checked or
+    /// recorded.
+    const ASCII_ART: [bool; 2] = [false, false];
+    /// Whether the puzzle has a second part. If not, `part2` is never called,
+    /// and the part is reported as unimplemented.
+    const PART2_EXISTS: bool = true;
+    /// Test inputs written in code, which are run after the `dayN-test-*.txt`
+    /// files.
+    const TEST_INPUTS: &'static [TestInput<'static>] = &[];
+
+    /// The parsed input, which may borrow from the input text. Shared between
+    /// the parts, which may run on different threads.
+    type Parsed<'a>: Sync;
+    type Output1: SolverOutput;
+    type Output2: SolverOutput;
+
+    /// Whether `parse` does any work. `false` for days whose parts parse the
+    /// input themselves, so that no parse time is reported for them.
+    const PARSES_INPUT: bool = true;
+
+    fn parse<'a>(&self, input: RawInput<'a>) -> Result<Self::Parsed<'a>, Box<dyn error::Error>>;
+
+    fn part1(&self, parsed: &Self::Parsed<'_>) -> Self::Output1;
+
+    fn part2(&self, parsed: &Self::Parsed<'_>) -> Self::Output2;
+}
+
+/// Fills in the parse phase of a `Day` whose parts parse the input
+/// themselves: `Parsed` is the `RawInput`, passed through unchanged, and no
+/// parse time is reported.
+///
+/// ```text
+/// impl Day for Day1 {
+///     const NUMBER: usize = 1;
+///     const TITLE: &'static str = "Calorie Counting";
+///
+///     type Output1 = u32;
+///     type Output2 = u32;
+///
+///     unparsed_input!();
+///
+///     fn part1(&self, input: &RawInput) -> u32 { ... }
+///     fn part2(&self, input: &RawInput) -> u32 { ... }
+/// }
+/// ```
+#[macro_export]
+macro_rules! unparsed_input {
+    () => {
+        type Parsed<'a> = $crate::harness::input::RawInput<'a>;
+
+        const PARSES_INPUT: bool = false;
+
+        fn parse<'a>(
+            &self,
+            input: $crate::harness::input::RawInput<'a>,
+        ) -> Result<$crate::harness::input::RawInput<'a>, Box<dyn std::error::Error>> {
+            Ok(input)
+        }
+    };
+}
+
+/// The number and title of a puzzle solved by `PartFns`, which can't come from
+/// the functions themselves.
+pub trait Puzzle {
+    const NUMBER: usize;
+    const TITLE: &'static str;
+}
+
+/// Adapts a pair of `fn(RawInput) -> O` part solvers, which each parse the
+/// input themselves, to `Day`. `P` names the puzzle. Modules written this way
+/// are registered in `with_days!` as `dayN::DayN(solve_part1, solve_part2)`.
+pub struct PartFns<P, F1, F2> {
+    part1: F1,
+    part2: F2,
+    puzzle: PhantomData<fn() -> P>,
+}
+
+impl<P, F1, F2> PartFns<P, F1, F2> {
+    pub const fn new(part1: F1, part2: F2) -> Self {
+        Self {
+            part1,
+            part2,
+            puzzle: PhantomData,
+        }
+    }
+}
+
+impl<P, F1, F2, O1, O2> Day for PartFns<P, F1, F2>
+where
+    P: Puzzle,
+    F1: Fn(RawInput) -> O1 + Sync,
+    F2: Fn(RawInput) -> O2 + Sync,
+    O1: SolverOutput,
+    O2: SolverOutput,
+{
+    const NUMBER: usize = P::NUMBER;
+    const TITLE: &'static str = P::TITLE;
+
+    type Output1 = O1;
+    type Output2 = O2;
+
+    unparsed_input!();
+
+    fn part1(&self, input: &RawInput) -> O1 {
+        (self.part1)(*input)
+    }
+
+    fn part2(&self, input: &RawInput) -> O2 {
+        (self.part2)(*input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sums;
+
+    impl Puzzle for Sums {
+        const NUMBER: usize = 1;
+        const TITLE: &'static str = "Sums";
+    }
+
+    fn solve_part1(input: RawInput) -> u32 {
+        input.per_line(|line| line.single::<u32>()).sum()
+    }
+
+    fn solve_part2(input: RawInput) -> usize {
+        input.per_line(|line| line.single::<u32>()).count()
+    }
+
+    #[test]
+    fn test_part_fns() {
+        let day = PartFns::<Sums, _, _>::new(solve_part1, solve_part2);
+        let parsed = day.parse(RawInput::new("1\n2\n3")).unwrap();
+        assert_eq!((day.part1(&parsed), day.part2(&parsed)), (6, 3));
+        assert_eq!(
+            <PartFns<Sums, fn(RawInput) -> u32, fn(RawInput) -> usize>>::TITLE,
+            "Sums"
+        );
+    }
+}
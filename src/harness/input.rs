@@ -1,105 +1,1127 @@
+#[cfg(unix)]
+use crate::harness::mmap::Mmap;
+use crate::regex;
 use crate::util::re;
 use crate::util::re::MatchTuple;
+use once_cell::sync::OnceCell;
 use regex::Regex;
-use std::fmt::Debug;
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::{error, fs, io, iter, str};
 
+/// An error parsing a single line of input, which says where the line is and
+/// what it contains.
+#[derive(Debug)]
+pub struct InputError {
+    pub source: Option<PathBuf>,
+    /// The 1-based line number, if known.
+    pub line_number: Option<usize>,
+    /// The 1-based index of the group containing the line, for inputs read
+    /// with `grouped_lines`.
+    pub group: Option<usize>,
+    pub line: String,
+    pub reason: Box<dyn error::Error>,
+}
+
+impl InputError {
+    pub fn new(line: &LineInput, reason: impl Into<Box<dyn error::Error>>) -> Self {
+        Self {
+            source: line.source.map(Path::to_path_buf),
+            line_number: line.number,
+            group: line.group,
+            line: line.text.to_owned(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Like `new`, but if `reason` is already an `InputError` then it is
+    /// returned unchanged, since it names a line already.
+    fn wrap(line: &LineInput, reason: impl Into<Box<dyn error::Error>>) -> Self {
+        match reason.into().downcast::<InputError>() {
+            Ok(error) => *error,
+            Err(reason) => Self::new(line, reason),
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (&self.source, self.line_number) {
+            (Some(source), Some(number)) => write!(f, "{}:{}", source.display(), number)?,
+            (Some(source), None) => write!(f, "{}", source.display())?,
+            (None, Some(number)) => write!(f, "Line {}", number)?,
+            (None, None) => {}
+        }
+        if let Some(group) = self.group {
+            write!(f, " (group {})", group)?;
+        }
+        if self.source.is_some() || self.line_number.is_some() {
+            write!(f, ": ")?;
+        }
+        write!(f, "Could not parse {:?}: {}", self.line, self.reason)
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.reason)
+    }
+}
+
+/// An error splitting an input into sections, or parsing one of them.
+#[derive(Debug)]
+pub struct SectionError {
+    pub source: Option<PathBuf>,
+    /// The 1-based line number that the section starts on, or that the whole
+    /// input starts on if it has the wrong number of sections.
+    pub line_number: usize,
+    /// The 1-based index of the section, if the error is in one.
+    pub section: Option<usize>,
+    pub reason: Box<dyn error::Error>,
+}
+
+impl SectionError {
+    /// Reports that `reason` happened in the `i`th section, which is 0-based,
+    /// unless `reason` is an `InputError` that names a line already.
+    fn wrap(section: &RawInput, i: usize, reason: Box<dyn error::Error>) -> Box<dyn error::Error> {
+        if reason.is::<InputError>() {
+            return reason;
+        }
+        Box::new(Self {
+            source: section.source.map(Path::to_path_buf),
+            line_number: section.first_line,
+            section: Some(i + 1),
+            reason,
+        })
+    }
+}
+
+impl Display for SectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            Some(source) => write!(f, "{}:{}: ", source.display(), self.line_number)?,
+            None => write!(f, "Line {}: ", self.line_number)?,
+        }
+        if let Some(section) = self.section {
+            write!(f, "Section {}: ", section)?;
+        }
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl error::Error for SectionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.reason)
+    }
+}
+
+/// A value that can be parsed from a whole input, or one of its sections.
+/// Implemented for `RawInput` itself, to parse a section later, and for every
+/// `FromStr` type, which is given the section's text.
+pub trait FromInput<'a>: Sized {
+    fn from_input(input: RawInput<'a>) -> Result<Self, Box<dyn error::Error>>;
+}
+
+impl<'a> FromInput<'a> for RawInput<'a> {
+    fn from_input(input: RawInput<'a>) -> Result<Self, Box<dyn error::Error>> {
+        Ok(input)
+    }
+}
+
+impl<'a, T> FromInput<'a> for T
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+{
+    fn from_input(input: RawInput<'a>) -> Result<Self, Box<dyn error::Error>> {
+        input.as_str().parse().map_err(Into::into)
+    }
+}
+
+/// A tuple of types to parse the sections of an input as, with
+/// `RawInput::parse_sections`.
+pub trait Sections<'a>: Sized {
+    const COUNT: usize;
+
+    /// Parses `sections`, of which there are `COUNT`.
+    fn parse_sections(sections: &[RawInput<'a>]) -> Result<Self, Box<dyn error::Error>>;
+}
+
+macro_rules! impl_sections {
+    ($count:literal: $($T:ident $i:tt),+) => {
+        impl<'a, $($T: FromInput<'a>),+> Sections<'a> for ($($T,)+) {
+            const COUNT: usize = $count;
+
+            fn parse_sections(sections: &[RawInput<'a>]) -> Result<Self, Box<dyn error::Error>> {
+                Ok(($(
+                    $T::from_input(sections[$i])
+                        .map_err(|error| SectionError::wrap(&sections[$i], $i, error))?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_sections!(1: A 0);
+impl_sections!(2: A 0, B 1);
+impl_sections!(3: A 0, B 1, C 2);
+impl_sections!(4: A 0, B 1, C 2, D 3);
+
+/// A single line of input, which remembers where it came from so that parse
+/// errors can point at it.
 #[derive(Copy, Clone, Debug)]
-pub struct LineInput<'a>(&'a str);
+pub struct LineInput<'a> {
+    text: &'a str,
+    source: Option<&'a Path>,
+    number: Option<usize>,
+    group: Option<usize>,
+}
 
 impl<'a> LineInput<'a> {
     pub fn new(s: &'a str) -> Self {
-        Self(s)
+        Self {
+            text: s,
+            source: None,
+            number: None,
+            group: None,
+        }
     }
 
     pub fn as_str(&self) -> &'a str {
-        self.0
+        self.text
+    }
+
+    /// The 1-based line number within the whole input.
+    pub fn number(&self) -> Option<usize> {
+        self.number
     }
 
+    /// The 1-based index of the group containing this line, when read with
+    /// `grouped_lines`.
+    pub fn group(&self) -> Option<usize> {
+        self.group
+    }
+
+    #[track_caller]
     pub fn single<T>(&self) -> T
     where
         T: FromStr,
-        <T as FromStr>::Err: Debug,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        unwrap_or_panic(self.try_single())
+    }
+
+    pub fn try_single<T>(&self) -> Result<T, InputError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
     {
-        self.0.parse().unwrap()
+        self.text.parse().map_err(|error| self.error(error))
     }
 
     pub fn chars(&self) -> Vec<char> {
-        self.0.chars().collect()
+        self.text.chars().collect()
     }
 
     pub fn bytes(&self) -> Vec<u8> {
-        self.0.bytes().collect()
+        self.text.bytes().collect()
     }
 
     pub fn digits(&self) -> Vec<u32> {
-        self.0.bytes().map(|b| (b - b'0') as u32).collect()
+        self.text.bytes().map(|b| (b - b'0') as u32).collect()
     }
 
+    pub fn try_digits(&self) -> Result<Vec<u32>, InputError> {
+        self.text
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .ok_or_else(|| self.error(format!("{:?} is not a digit.", c)))
+            })
+            .collect()
+    }
+
+    #[track_caller]
     pub fn split<T>(&self, pattern: &str) -> Vec<T>
     where
         T: FromStr,
-        <T as FromStr>::Err: Debug,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
     {
-        self.0.split(pattern).map(|s| s.parse().unwrap()).collect()
+        unwrap_or_panic(self.try_split(pattern))
     }
 
+    #[track_caller]
     pub fn split_whitespace<T>(&self) -> Vec<T>
     where
         T: FromStr,
-        <T as FromStr>::Err: Debug,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        unwrap_or_panic(self.try_split_whitespace())
+    }
+
+    pub fn try_split<T>(&self, pattern: &str) -> Result<Vec<T>, InputError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        self.text
+            .split(pattern)
+            .enumerate()
+            .map(|(i, s)| {
+                s.parse()
+                    .map_err(|error| self.field_error("Field", i, s, error))
+            })
+            .collect()
+    }
+
+    pub fn try_split_whitespace<T>(&self) -> Result<Vec<T>, InputError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
     {
-        self.0
+        self.text
             .split_ascii_whitespace()
-            .map(|s| s.parse().unwrap())
+            .enumerate()
+            .map(|(i, s)| {
+                s.parse()
+                    .map_err(|error| self.field_error("Field", i, s, error))
+            })
             .collect()
     }
 
+    /// Every integer in the line, including negative ones, ignoring the text
+    /// around them.
+    #[track_caller]
+    pub fn ints<T>(&self) -> Vec<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        unwrap_or_panic(self.try_ints())
+    }
+
+    pub fn try_ints<T>(&self) -> Result<Vec<T>, InputError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        self.parse_matches(regex!(r"[-+]?\d+"), "Integer")
+    }
+
+    /// Like `ints`, but a `-` before a number is text rather than a sign, for
+    /// lines like `2-4,6-8`.
+    #[track_caller]
+    pub fn uints<T>(&self) -> Vec<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        unwrap_or_panic(self.try_uints())
+    }
+
+    pub fn try_uints<T>(&self) -> Result<Vec<T>, InputError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        self.parse_matches(regex!(r"\d+"), "Integer")
+    }
+
+    /// Like `ints`, for lines with exactly `N` integers.
+    #[track_caller]
+    pub fn ints_array<T, const N: usize>(&self) -> [T; N]
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        unwrap_or_panic(self.try_ints_array())
+    }
+
+    pub fn try_ints_array<T, const N: usize>(&self) -> Result<[T; N], InputError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        let ints = self.try_ints()?;
+        let found = ints.len();
+        ints.try_into()
+            .map_err(|_| self.error(format!("Expected {} integers, found {}.", N, found)))
+    }
+
+    pub fn words(&self) -> Vec<&'a str> {
+        self.text.split_ascii_whitespace().collect()
+    }
+
+    /// Splits the line at the first `delimiter` and parses both sides.
+    #[track_caller]
+    pub fn split_once_parse<A, B>(&self, delimiter: &str) -> (A, B)
+    where
+        A: FromStr,
+        <A as FromStr>::Err: Into<Box<dyn error::Error>>,
+        B: FromStr,
+        <B as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        unwrap_or_panic(self.try_split_once_parse(delimiter))
+    }
+
+    pub fn try_split_once_parse<A, B>(&self, delimiter: &str) -> Result<(A, B), InputError>
+    where
+        A: FromStr,
+        <A as FromStr>::Err: Into<Box<dyn error::Error>>,
+        B: FromStr,
+        <B as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        let (a, b) = self
+            .text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(format!("Missing {:?}.", delimiter)))?;
+        let a = a
+            .parse()
+            .map_err(|error| self.field_error("Field", 0, a, error))?;
+        let b = b
+            .parse()
+            .map_err(|error| self.field_error("Field", 1, b, error))?;
+        Ok((a, b))
+    }
+
+    /// Parses the value written as `key=value` in the line, such as `y` in
+    /// `x=-2, y=15`. The value ends at whitespace, `,`, `;` or `:`.
+    #[track_caller]
+    pub fn value_of<T>(&self, key: &str) -> T
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        unwrap_or_panic(self.try_value_of(key))
+    }
+
+    pub fn try_value_of<T>(&self, key: &str) -> Result<T, InputError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        let value = self
+            .key_values()
+            .find(|&(k, _)| k == key)
+            .map(|(_, value)| value)
+            .ok_or_else(|| self.error(format!("Missing {}=.", key)))?;
+        value.parse().map_err(|error: <T as FromStr>::Err| {
+            self.error(format!("{}={:?}: {}", key, value, error.into()))
+        })
+    }
+
+    /// Every `key=value` pair in the line, in order.
+    pub fn key_values(&self) -> impl Iterator<Item = (&'a str, &'a str)> {
+        regex!(r"(\w+) *= *([^\s,;:]*)")
+            .captures_iter(self.text)
+            .map(|caps| (caps.get(1).unwrap().as_str(), caps.get(2).unwrap().as_str()))
+    }
+
+    #[track_caller]
     pub fn parse_with_regex<T>(&self, re: &Regex) -> T
     where
         T: MatchTuple<'a>,
     {
-        re::parse_with_regex(re, self.0).unwrap()
+        unwrap_or_panic(self.try_parse_with_regex(re))
+    }
+
+    pub fn try_parse_with_regex<T>(&self, re: &Regex) -> Result<T, InputError>
+    where
+        T: MatchTuple<'a>,
+    {
+        re::parse_with_regex(re, self.text).map_err(|error| self.error(error))
+    }
+
+    fn error(&self, reason: impl Into<Box<dyn error::Error>>) -> InputError {
+        InputError::new(self, reason)
+    }
+
+    /// An error for the `i`th field of a line, which is 0-based. `noun` is
+    /// what the fields are, such as `"Field"` or `"Integer"`.
+    fn field_error(
+        &self,
+        noun: &str,
+        i: usize,
+        field: &str,
+        reason: impl Into<Box<dyn error::Error>>,
+    ) -> InputError {
+        self.error(format!(
+            "{} {} ({:?}): {}",
+            noun,
+            i + 1,
+            field,
+            reason.into()
+        ))
+    }
+
+    /// Parses every match of `re`, naming a match that fails with `noun`.
+    fn parse_matches<T>(&self, re: &Regex, noun: &str) -> Result<Vec<T>, InputError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+    {
+        re.find_iter(self.text)
+            .enumerate()
+            .map(|(i, m)| {
+                let s = m.as_str();
+                s.parse()
+                    .map_err(|error| self.field_error(noun, i, s, error))
+            })
+            .collect()
+    }
+}
+
+/// Unwraps the result of a fallible helper, panicking with the error's message
+/// rather than its `Debug` form so that the line is easy to find. Reported as
+/// a panic in the day's code, which called the helper.
+#[track_caller]
+fn unwrap_or_panic<T>(result: Result<T, InputError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Files at least this large are mapped or streamed rather than read into
+/// memory.
+const LARGE_FILE_SIZE: u64 = 64 << 20;
+
+/// How many bytes of a streamed input `chars` decodes at a time.
+const CHUNK_SIZE: usize = 8 << 10;
+
+/// The text of a puzzle input, which `raw_input` borrows. Small inputs are
+/// read into memory. Large files are mapped into memory where the platform
+/// supports it, and otherwise, or if mapping fails, streamed from the file
+/// whenever they are iterated over, so they aren't copied unless a day asks
+/// for the whole text.
+pub struct InputText {
+    path: Option<PathBuf>,
+    contents: Contents,
+}
+
+enum Contents {
+    Read(String),
+    /// Checked to be UTF-8 when mapped.
+    #[cfg(unix)]
+    Mapped(Mmap),
+    /// Read on demand. Holds the whole text once something asks for it.
+    Streamed(OnceCell<String>),
+}
+
+impl InputText {
+    pub fn read_file(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let contents = if file.metadata()?.len() < LARGE_FILE_SIZE {
+            Contents::Read(io::read_to_string(file)?)
+        } else {
+            Self::large_file(&file)?
+        };
+        Ok(Self {
+            path: Some(path.to_owned()),
+            contents,
+        })
+    }
+
+    pub fn read_stdin() -> io::Result<Self> {
+        Ok(Self {
+            path: None,
+            contents: Contents::Read(io::read_to_string(io::stdin())?),
+        })
+    }
+
+    /// Maps `file` into memory, or streams it if it can't be mapped.
+    #[cfg(unix)]
+    fn large_file(file: &File) -> io::Result<Contents> {
+        // SAFETY: The harness never writes to input files, and `--watch` waits
+        // for a run to finish before it reruns the day for a changed input.
+        // Editing an input by hand while a day is running on it is not
+        // supported.
+        match unsafe { Mmap::map(file) } {
+            Ok(map) => {
+                str::from_utf8(&map)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                Ok(Contents::Mapped(map))
+            }
+            // Some files, such as those on some network filesystems, can't be
+            // mapped.
+            Err(_) => Ok(Contents::Streamed(OnceCell::new())),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn large_file(_file: &File) -> io::Result<Contents> {
+        Ok(Contents::Streamed(OnceCell::new()))
+    }
+
+    /// The input, naming the file it was read from in parse errors.
+    pub fn raw_input(&self) -> RawInput<'_> {
+        let text = match &self.contents {
+            Contents::Read(text) => Text::Borrowed(text),
+            // SAFETY: The map was checked to be UTF-8 when it was created.
+            #[cfg(unix)]
+            Contents::Mapped(map) => Text::Borrowed(unsafe { str::from_utf8_unchecked(map) }),
+            Contents::Streamed(loaded) => Text::Streamed {
+                path: self.path.as_deref().unwrap(),
+                loaded,
+            },
+        };
+        let input = RawInput {
+            text,
+            source: None,
+            first_line: 1,
+        };
+        match &self.path {
+            Some(path) => input.with_source(path, 1),
+            None => input,
+        }
     }
 }
 
 #[derive(Copy, Clone, Debug)]
-pub struct RawInput<'a>(&'a str);
+pub struct RawInput<'a> {
+    text: Text<'a>,
+    source: Option<&'a Path>,
+    first_line: usize,
+}
+
+/// Where the text of a `RawInput` comes from.
+#[derive(Copy, Clone, Debug)]
+enum Text<'a> {
+    Borrowed(&'a str),
+    /// Read from `path` a line at a time each time the input is iterated
+    /// over, or into `loaded` if the whole text is asked for.
+    Streamed {
+        path: &'a Path,
+        loaded: &'a OnceCell<String>,
+    },
+}
+
+/// A line of input and where it is. Lines of a streamed input are owned, since
+/// they are read into a new buffer.
+struct Line<'a> {
+    text: Cow<'a, str>,
+    number: usize,
+    group: Option<usize>,
+}
+
+impl Line<'_> {
+    fn input<'b>(&'b self, source: Option<&'b Path>) -> LineInput<'b> {
+        LineInput {
+            text: &self.text,
+            source,
+            number: Some(self.number),
+            group: self.group,
+        }
+    }
+}
 
 impl<'a> RawInput<'a> {
     pub fn new(s: &'a str) -> Self {
-        Self(s)
+        Self {
+            text: Text::Borrowed(s),
+            source: None,
+            first_line: 1,
+        }
+    }
+
+    /// Names the file the input was read from in parse errors. `first_line` is
+    /// the line of the file that the input starts on, for inputs that follow a
+    /// header.
+    pub fn with_source(self, source: &'a Path, first_line: usize) -> Self {
+        Self {
+            source: Some(source),
+            first_line,
+            ..self
+        }
     }
 
+    /// The whole text. For a streamed input, this reads the whole file into
+    /// memory the first time it is called.
     pub fn as_str(&self) -> &'a str {
-        self.0
+        match self.text {
+            Text::Borrowed(text) => text,
+            Text::Streamed { path, loaded } => loaded.get_or_init(|| {
+                fs::read_to_string(path).unwrap_or_else(|error| read_failed(path, error))
+            }),
+        }
+    }
+
+    pub fn source(&self) -> Option<&'a Path> {
+        self.source
+    }
+
+    /// The bytes of the input, read as they are needed if it is streamed.
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + 'a {
+        let bytes: Box<dyn Iterator<Item = u8>> = match self.streamed_path() {
+            Some(path) => Box::new(
+                open(path)
+                    .bytes()
+                    .map(move |byte| byte.unwrap_or_else(|error| read_failed(path, error))),
+            ),
+            None => Box::new(self.as_str().bytes()),
+        };
+        bytes
+    }
+
+    /// The characters of the input, read as they are needed if it is
+    /// streamed.
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        let chars: Box<dyn Iterator<Item = char>> = match self.streamed_path() {
+            Some(path) => Box::new(StreamedChars::new(path, CHUNK_SIZE)),
+            None => Box::new(self.as_str().chars()),
+        };
+        chars
     }
 
     pub fn single_line<F, T>(&self, f: F) -> T
     where
         F: Fn(LineInput) -> T,
     {
-        let line = self.0.lines().next().unwrap();
-        f(LineInput(line))
+        let line = self.lines().next().unwrap();
+        f(line.input(self.source))
+    }
+
+    /// Like `single_line`, but for a function that can fail. Errors that don't
+    /// already name a line are reported with the first line.
+    pub fn try_single_line<F, T, E>(&self, f: F) -> Result<T, InputError>
+    where
+        F: Fn(LineInput) -> Result<T, E>,
+        E: Into<Box<dyn error::Error>>,
+    {
+        let line = self.lines().next().unwrap_or(Line {
+            text: Cow::Borrowed(""),
+            number: self.first_line,
+            group: None,
+        });
+        let line = line.input(self.source);
+        f(line).map_err(|error| InputError::wrap(&line, error))
     }
 
     pub fn per_line<F, T>(&self, f: F) -> impl Iterator<Item = T> + 'a
     where
         F: Fn(LineInput) -> T + 'static,
     {
-        self.0.lines().map(move |line| f(LineInput(line)))
+        let source = self.source;
+        self.lines().map(move |line| f(line.input(source)))
     }
 
     pub fn grouped_lines<F, T>(&self, f: F) -> impl Iterator<Item = Vec<T>> + 'a
     where
         F: Fn(LineInput) -> T + 'static,
     {
-        self.0
-            .split("\n\n")
-            .map(move |group| group.lines().map(|line| f(LineInput(line))).collect())
+        let source = self.source;
+        self.groups()
+            .map(move |group| group.iter().map(|line| f(line.input(source))).collect())
+    }
+
+    /// Like `per_line`, but for a function that can fail. Errors that don't
+    /// already name a line are reported with the line being parsed.
+    pub fn try_per_line<F, T, E>(&self, f: F) -> impl Iterator<Item = Result<T, InputError>> + 'a
+    where
+        F: Fn(LineInput) -> Result<T, E> + 'static,
+        E: Into<Box<dyn error::Error>>,
+    {
+        let source = self.source;
+        self.lines().map(move |line| {
+            let line = line.input(source);
+            f(line).map_err(|error| InputError::wrap(&line, error))
+        })
+    }
+
+    /// Like `grouped_lines`, but for a function that can fail. Each group stops
+    /// at its first error.
+    pub fn try_grouped_lines<F, T, E>(
+        &self,
+        f: F,
+    ) -> impl Iterator<Item = Result<Vec<T>, InputError>> + 'a
+    where
+        F: Fn(LineInput) -> Result<T, E> + 'static,
+        E: Into<Box<dyn error::Error>>,
+    {
+        let source = self.source;
+        self.groups().map(move |group| {
+            group
+                .iter()
+                .map(|line| {
+                    let line = line.input(source);
+                    f(line).map_err(|error| InputError::wrap(&line, error))
+                })
+                .collect()
+        })
+    }
+
+    /// Splits the input into sections separated by blank lines, such as a
+    /// picture followed by a list of instructions. Runs of blank lines count as
+    /// one, and blank lines before the first section or after the last are
+    /// ignored. Each section names the line it starts on in parse errors, and
+    /// doesn't include the line ending of its last line. A streamed input is
+    /// read whole first.
+    pub fn sections(&self) -> impl Iterator<Item = RawInput<'a>> + 'a {
+        let Self {
+            source, first_line, ..
+        } = *self;
+        let text = self.as_str();
+        let mut offset = 0;
+        let mut lines = text
+            .split_inclusive('\n')
+            .map(move |line| {
+                let start = offset;
+                offset += line.len();
+                (start, line_content(line))
+            })
+            .enumerate()
+            .peekable();
+        let is_blank = |&(_, (_, line)): &(usize, (usize, &str))| line.is_empty();
+        iter::from_fn(move || {
+            while lines.next_if(is_blank).is_some() {}
+            let &(i, (start, _)) = lines.peek()?;
+            let mut end = start;
+            while let Some((_, (line_start, line))) = lines.next_if(|line| !is_blank(line)) {
+                end = line_start + line.len();
+            }
+            Some(RawInput {
+                text: Text::Borrowed(&text[start..end]),
+                source,
+                first_line: first_line + i,
+            })
+        })
+    }
+
+    /// Splits the input with `sections` and parses each section as the type
+    /// in the same position of `T`, a tuple. Errors name the section that
+    /// failed to parse, unless they already name a line.
+    pub fn parse_sections<T: Sections<'a>>(&self) -> Result<T, Box<dyn error::Error>> {
+        let sections: Vec<_> = self.sections().collect();
+        if sections.len() != T::COUNT {
+            return Err(Box::new(SectionError {
+                source: self.source.map(Path::to_path_buf),
+                line_number: self.first_line,
+                section: None,
+                reason: format!(
+                    "Expected {} sections separated by blank lines, found {}.",
+                    T::COUNT,
+                    sections.len()
+                )
+                .into(),
+            }));
+        }
+        T::parse_sections(&sections)
     }
 
     pub fn raw_str(&self) -> &str {
-        self.0
+        self.as_str()
+    }
+
+    /// The path to read from, if the input is streamed and hasn't been read
+    /// whole yet.
+    fn streamed_path(&self) -> Option<&'a Path> {
+        match self.text {
+            Text::Streamed { path, loaded } if loaded.get().is_none() => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Every line, numbered from `first_line`.
+    fn lines(&self) -> impl Iterator<Item = Line<'a>> + 'a {
+        let texts: Box<dyn Iterator<Item = Cow<'a, str>>> = match self.streamed_path() {
+            Some(path) => Box::new(open(path).lines().map(move |text| {
+                Cow::Owned(text.unwrap_or_else(|error| read_failed(path, error)))
+            })),
+            None => Box::new(self.as_str().lines().map(Cow::Borrowed)),
+        };
+        let first_line = self.first_line;
+        texts.enumerate().map(move |(i, text)| Line {
+            text,
+            number: first_line + i,
+            group: None,
+        })
+    }
+
+    /// The lines of each group of lines, split the same way as `sections`.
+    /// Lines are numbered within the whole input, and groups are numbered from
+    /// 1.
+    fn groups(&self) -> impl Iterator<Item = Vec<Line<'a>>> + 'a {
+        let mut lines = self.lines().peekable();
+        let mut group = 0;
+        iter::from_fn(move || {
+            while lines.next_if(|line| line.text.is_empty()).is_some() {}
+            lines.peek()?;
+            group += 1;
+            let mut lines_in_group = vec![];
+            while let Some(line) = lines.next_if(|line| !line.text.is_empty()) {
+                lines_in_group.push(Line {
+                    group: Some(group),
+                    ..line
+                });
+            }
+            Some(lines_in_group)
+        })
+    }
+}
+
+/// A line without its `\n` or `\r\n` ending.
+fn line_content(line: &str) -> &str {
+    match line.strip_suffix('\n') {
+        Some(line) => line.strip_suffix('\r').unwrap_or(line),
+        None => line,
+    }
+}
+
+/// Opens a streamed input. It was readable when the input was created, so
+/// failures are unexpected and panic.
+fn open(path: &Path) -> BufReader<File> {
+    BufReader::new(File::open(path).unwrap_or_else(|error| read_failed(path, error)))
+}
+
+fn read_failed(path: &Path, error: io::Error) -> ! {
+    panic!("Could not read {}: {}", path.display(), error)
+}
+
+/// The characters of a streamed input, decoded a chunk at a time so that only
+/// one chunk is held in memory however long the lines are.
+struct StreamedChars<'a> {
+    path: &'a Path,
+    reader: BufReader<File>,
+    chunk_size: usize,
+    /// Bytes read but not yet decoded: the start of a character that was cut
+    /// off by the end of the last chunk.
+    undecoded: Vec<u8>,
+    decoded: String,
+    position: usize,
+}
+
+impl<'a> StreamedChars<'a> {
+    fn new(path: &'a Path, chunk_size: usize) -> Self {
+        Self {
+            path,
+            reader: open(path),
+            chunk_size,
+            undecoded: Vec::with_capacity(chunk_size + 3),
+            decoded: String::with_capacity(chunk_size + 3),
+            position: 0,
+        }
+    }
+
+    /// Decodes the next chunk into `decoded`. Returns `false` at the end of
+    /// the file.
+    fn read_chunk(&mut self) -> bool {
+        let start = self.undecoded.len();
+        self.undecoded.resize(start + self.chunk_size, 0);
+        let read = loop {
+            match self.reader.read(&mut self.undecoded[start..]) {
+                Ok(read) => break read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => read_failed(self.path, error),
+            }
+        };
+        self.undecoded.truncate(start + read);
+        if read == 0 && start == 0 {
+            return false;
+        }
+        let valid = match str::from_utf8(&self.undecoded) {
+            Ok(text) => text.len(),
+            // The last character continues in the next chunk.
+            Err(error) if error.error_len().is_none() && read > 0 => error.valid_up_to(),
+            Err(error) => read_failed(self.path, io::Error::new(io::ErrorKind::InvalidData, error)),
+        };
+        self.decoded.clear();
+        self.decoded
+            .push_str(str::from_utf8(&self.undecoded[..valid]).unwrap());
+        self.position = 0;
+        self.undecoded.drain(..valid);
+        true
+    }
+}
+
+impl Iterator for StreamedChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if let Some(c) = self.decoded[self.position..].chars().next() {
+                self.position += c.len_utf8();
+                return Some(c);
+            }
+            if !self.read_chunk() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, panic, process};
+
+    #[test]
+    fn test_try_per_line() {
+        let input = RawInput::new("1 2\n3 x\n5 6");
+        let lines: Vec<_> = input
+            .try_per_line(|line| line.try_split_whitespace::<u32>())
+            .collect();
+        assert_eq!(lines[0].as_ref().unwrap(), &vec![1, 2]);
+        assert_eq!(
+            lines[1].as_ref().unwrap_err().to_string(),
+            r#"Line 2: Could not parse "3 x": Field 2 ("x"): invalid digit found in string"#
+        );
+    }
+
+    #[test]
+    fn test_try_per_line_wraps_other_errors() {
+        let input = RawInput::new("a");
+        let error = input
+            .try_per_line(|_| Err::<(), _>("Bad line."))
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Line 1: Could not parse "a": Bad line."#
+        );
+    }
+
+    #[test]
+    fn test_extraction_helpers() {
+        let line = LineInput::new("Sensor at x=2, y=-18: closest beacon is at x=-2, y=+15");
+        assert_eq!(line.ints::<i32>(), vec![2, -18, -2, 15]);
+        assert_eq!(line.ints_array::<i32, 4>(), [2, -18, -2, 15]);
+        assert_eq!(line.value_of::<i32>("y"), -18);
+        assert_eq!(LineInput::new("2-4,6-8").uints::<u8>(), vec![2, 4, 6, 8]);
+        assert_eq!(
+            LineInput::new("move 1 from 2").words(),
+            ["move", "1", "from", "2"]
+        );
+        assert_eq!(
+            LineInput::new("a -> 7").split_once_parse::<char, u32>(" -> "),
+            ('a', 7)
+        );
+        let line = LineInput {
+            number: Some(3),
+            ..LineInput::new("1 2 300")
+        };
+        assert_eq!(
+            line.try_ints::<u8>().unwrap_err().to_string(),
+            r#"Line 3: Could not parse "1 2 300": Integer 3 ("300"): number too large to fit in target type"#
+        );
+        assert_eq!(
+            line.try_ints_array::<u32, 2>().unwrap_err().to_string(),
+            r#"Line 3: Could not parse "1 2 300": Expected 2 integers, found 3."#
+        );
+        assert_eq!(
+            line.try_split_once_parse::<u32, u32>(",")
+                .unwrap_err()
+                .to_string(),
+            r#"Line 3: Could not parse "1 2 300": Missing ","."#
+        );
+        assert_eq!(
+            line.try_value_of::<u32>("x").unwrap_err().to_string(),
+            r#"Line 3: Could not parse "1 2 300": Missing x=."#
+        );
+    }
+
+    #[test]
+    fn test_grouped_lines_numbering() {
+        let input = RawInput::new("1\n2\n\n3\n\n4\nx").with_source(Path::new("day1.txt"), 1);
+        let groups: Vec<_> = input
+            .grouped_lines(|line| (line.group(), line.number()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                vec![(Some(1), Some(1)), (Some(1), Some(2))],
+                vec![(Some(2), Some(4))],
+                vec![(Some(3), Some(6)), (Some(3), Some(7))],
+            ]
+        );
+        let error = input
+            .try_grouped_lines(|line| line.try_single::<u32>())
+            .nth(2)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"day1.txt:7 (group 3): Could not parse "x": invalid digit found in string"#
+        );
+    }
+
+    #[test]
+    fn test_large_inputs_match_borrowed() {
+        let text = "1\n2\n\n3\r\n\né\n";
+        let path = env::temp_dir().join(format!("aoc-input-test-{}.txt", process::id()));
+        fs::write(&path, text).unwrap();
+        let mut inputs = vec![InputText {
+            path: Some(path.clone()),
+            contents: Contents::Streamed(OnceCell::new()),
+        }];
+        if cfg!(unix) {
+            inputs.push(InputText {
+                path: Some(path.clone()),
+                contents: InputText::large_file(&File::open(&path).unwrap()).unwrap(),
+            });
+        }
+        let borrowed = RawInput::new(text).with_source(&path, 1);
+        let lines = |input: RawInput| -> Vec<_> {
+            input
+                .grouped_lines(|line| (line.as_str().to_owned(), line.number(), line.group()))
+                .collect()
+        };
+        for input in &inputs {
+            let input = input.raw_input();
+            assert_eq!(lines(input), lines(borrowed));
+            assert_eq!(input.chars().collect::<String>(), text);
+            assert_eq!(input.bytes().collect::<Vec<_>>(), text.as_bytes());
+            assert_eq!(input.as_str(), text);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_streamed_chars_across_chunks() {
+        let text = "a€b\néé\n";
+        let path = env::temp_dir().join(format!("aoc-chars-test-{}.txt", process::id()));
+        fs::write(&path, text).unwrap();
+        for chunk_size in 1..=5 {
+            let chars: String = StreamedChars::new(&path, chunk_size).collect();
+            assert_eq!(chars, text);
+        }
+        fs::write(&path, b"ab\xe2\x82").unwrap();
+        let result = panic::catch_unwind(|| StreamedChars::new(&path, 2).count());
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sections() {
+        let path = Path::new("day5.txt");
+        let text = "\r\n a\r\n b\r\n\r\n\r\n1\r\n2\r\n\r\n";
+        let input = RawInput::new(text).with_source(path, 3);
+        let sections: Vec<_> = input
+            .sections()
+            .map(|section| (section.as_str(), section.first_line))
+            .collect();
+        assert_eq!(sections, vec![(" a\r\n b", 4), ("1\r\n2", 8)]);
+        let (picture, numbers): (String, RawInput) = input.parse_sections().unwrap();
+        assert_eq!(picture, " a\r\n b");
+        assert_eq!(
+            numbers.per_line(|line| line.single()).collect::<Vec<u32>>(),
+            vec![1, 2]
+        );
+        let error = input
+            .parse_sections::<(RawInput, RawInput, RawInput)>()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day5.txt:3: Expected 3 sections separated by blank lines, found 2."
+        );
+        let error = input.parse_sections::<(String, u32)>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day5.txt:8: Section 2: invalid digit found in string"
+        );
     }
 }
@@ -0,0 +1,73 @@
+//! A read-only memory map of a file, so that large inputs can be borrowed as
+//! a `&str` without being copied into memory.
+
+use std::fs::File;
+use std::ops::Deref;
+use std::os::unix::io::AsRawFd;
+use std::{io, ptr, slice};
+
+pub struct Mmap {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+// The mapping is read-only, and `map`'s caller promises that the file won't
+// change under it, so it can be shared between threads like a `&[u8]`.
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+impl Mmap {
+    /// Maps the whole of `file`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or written to, by this process or any
+    /// other, until the map is dropped. Reading a page that a truncation
+    /// removed raises `SIGBUS`, and a write changes bytes that are already
+    /// borrowed as immutable.
+    pub unsafe fn map(file: &File) -> io::Result<Self> {
+        let len = file.metadata()?.len() as usize;
+        // Empty mappings aren't allowed.
+        if len == 0 {
+            return Ok(Self {
+                ptr: ptr::null_mut(),
+                len,
+            });
+        }
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { ptr, len })
+    }
+}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            unsafe {
+                libc::munmap(self.ptr, self.len);
+            }
+        }
+    }
+}
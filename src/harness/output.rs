@@ -0,0 +1,139 @@
+//! Captures what solvers print. `days.rs` shadows `print!` and `println!` for
+//! every day module with versions that call `print` here, which writes to the
+//! current thread's capture if there is one, and to stdout otherwise.
+//!
+//! Anything else written to stdout, such as from other threads a solver
+//! starts, isn't captured. `StdoutToStderr` keeps it out of machine-readable
+//! reports.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::fmt::Write;
+use std::io;
+
+/// Where `print` sends text on the current thread.
+enum Capture {
+    Collect(String),
+    Discard,
+}
+
+thread_local! {
+    static CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
+}
+
+/// Calls `f`, and returns its result along with everything it printed.
+pub fn capturing<O>(f: impl FnOnce() -> O) -> (O, String) {
+    let (output, printed) = with_capture(Capture::Collect(String::new()), f);
+    match printed {
+        Some(Capture::Collect(printed)) => (output, printed),
+        _ => (output, String::new()),
+    }
+}
+
+/// Calls `f`, throwing away everything it prints rather than keeping it.
+pub fn discarding<O>(f: impl FnOnce() -> O) -> O {
+    with_capture(Capture::Discard, f).0
+}
+
+fn with_capture<O>(capture: Capture, f: impl FnOnce() -> O) -> (O, Option<Capture>) {
+    /// Restores the previous capture even if `f` panics.
+    struct Restore(Option<Capture>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CAPTURE.with(|capture| *capture.borrow_mut() = self.0.take());
+        }
+    }
+
+    let previous = CAPTURE.with(|current| current.borrow_mut().replace(capture));
+    let restore = Restore(previous);
+    let output = f();
+    let captured = CAPTURE.with(|capture| capture.borrow_mut().take());
+    drop(restore);
+    (output, captured)
+}
+
+#[doc(hidden)]
+pub fn print(args: fmt::Arguments) {
+    CAPTURE.with(|capture| match &mut *capture.borrow_mut() {
+        Some(Capture::Collect(printed)) => printed.write_fmt(args).unwrap(),
+        Some(Capture::Discard) => {}
+        None => std::print!("{}", args),
+    })
+}
+
+/// Sends everything written to this process's stdout to stderr instead, until
+/// dropped. This covers output that `capturing` misses, including from other
+/// threads and child processes, so that stdout holds only a report.
+pub struct StdoutToStderr {
+    #[cfg(unix)]
+    saved: libc::c_int,
+}
+
+impl StdoutToStderr {
+    #[cfg(unix)]
+    pub fn start() -> io::Result<Self> {
+        io::Write::flush(&mut io::stdout())?;
+        // SAFETY: These calls only duplicate and replace file descriptors.
+        unsafe {
+            let saved = libc::dup(libc::STDOUT_FILENO);
+            if saved < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) < 0 {
+                let error = io::Error::last_os_error();
+                libc::close(saved);
+                return Err(error);
+            }
+            Ok(Self { saved })
+        }
+    }
+
+    /// Not supported, so stdout is left alone.
+    #[cfg(not(unix))]
+    pub fn start() -> io::Result<Self> {
+        Ok(Self {})
+    }
+}
+
+#[cfg(unix)]
+impl Drop for StdoutToStderr {
+    fn drop(&mut self) {
+        let _ = io::Write::flush(&mut io::stdout());
+        // SAFETY: `saved` is a descriptor that only this value owns.
+        unsafe {
+            libc::dup2(self.saved, libc::STDOUT_FILENO);
+            libc::close(self.saved);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capturing() {
+        let (output, printed) = capturing(|| {
+            print(format_args!("a{}\n", 1));
+            let ((), inner) = capturing(|| print(format_args!("b\n")));
+            assert_eq!(inner, "b\n");
+            print(format_args!("c\n"));
+            2
+        });
+        assert_eq!(output, 2);
+        assert_eq!(printed, "a1\nc\n");
+    }
+
+    #[test]
+    fn test_discarding() {
+        let (output, printed) = capturing(|| {
+            print(format_args!("a\n"));
+            discarding(|| print(format_args!("b\n")));
+            print(format_args!("c\n"));
+            2
+        });
+        assert_eq!(output, 2);
+        assert_eq!(printed, "a\nc\n");
+    }
+}
@@ -1,75 +1,177 @@
 use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Display, Formatter};
 use std::panic;
 use std::panic::UnwindSafe;
-use std::sync::{Arc, Mutex};
+use std::sync::Once;
 
-/// Calls the provided function and wraps its result in `Some`. If it panics due
-/// to a `todo!()` call, then returns `None`. Any other panic proceeds normally.
-pub fn catching_todo<F, O>(f: F) -> Option<O>
+thread_local! {
+    /// How many `catching_panics` calls are active on this thread.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Where the last panic caught on this thread happened, as `file:line:column`.
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Why a call to `catching_panics` panicked.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Panic {
+    /// A `todo!()` or `unimplemented!()` call.
+    Unimplemented,
+    /// Any other panic, with its message and where it happened.
+    Other {
+        message: String,
+        location: Option<String>,
+    },
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Panic::Unimplemented => write!(f, "Not implemented"),
+            Panic::Other {
+                message,
+                location: Some(location),
+            } => write!(f, "Panicked at {}: {}", location, message),
+            Panic::Other {
+                message,
+                location: None,
+            } => write!(f, "Panicked: {}", message),
+        }
+    }
+}
+
+/// Calls the provided function and returns its result, or how it panicked.
+/// Panics are caught rather than printed, so that one bad input can be
+/// reported like any other error instead of ending the process. Their
+/// location is kept, since it is usually the only clue to which line failed.
+///
+/// Safe to call from several threads at once: the panic hook is installed only
+/// once, and silences panics only on threads that are inside a call.
+pub fn catching_panics<F, O>(f: F) -> Result<O, Panic>
 where
-    F: UnwindSafe + Fn() -> O,
+    F: UnwindSafe + FnOnce() -> O,
 {
-    let old_hook = Arc::new(Mutex::new(Some(panic::take_hook())));
-    panic::set_hook({
-        let old_hook = Arc::clone(&old_hook);
-        Box::new(move |info| {
-            if !is_todo(info.payload()) {
-                if let Some(hook) = &*old_hook.lock().unwrap() {
-                    hook(info)
-                }
+    INSTALL_HOOK.call_once(|| {
+        let old_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) == 0 {
+                old_hook(info)
+            } else {
+                let location = info.location().map(ToString::to_string);
+                LOCATION.with(|last| *last.borrow_mut() = location);
             }
-        })
+        }));
     });
+    CATCHING.with(|catching| catching.set(catching.get() + 1));
     let result = panic::catch_unwind(f);
-    let old_hook = old_hook.lock().unwrap().take().unwrap();
-    panic::set_hook(old_hook);
-    match result {
-        Ok(o) => Some(o),
-        Err(error) => {
-            if is_todo(&*error) {
-                None
-            } else {
-                panic::resume_unwind(error)
-            }
+    CATCHING.with(|catching| catching.set(catching.get() - 1));
+    result.map_err(|payload| {
+        let message = message(&*payload);
+        let location = LOCATION.with(|last| last.borrow_mut().take());
+        if is_unimplemented(&message) {
+            Panic::Unimplemented
+        } else {
+            Panic::Other { message, location }
         }
-    }
+    })
 }
 
-fn is_todo(error: &dyn Any) -> bool {
-    if let Some(&s) = error.downcast_ref::<&str>() {
-        s == "not yet implemented"
-    } else if let Some(s) = error.downcast_ref::<String>() {
-        s.starts_with("not yet implemented: ")
+fn message(payload: &dyn Any) -> String {
+    // `panic!("message")` produces a `&str` payload when the message has no
+    // format arguments, so either payload type may carry a message.
+    if let Some(&s) = payload.downcast_ref::<&str>() {
+        s.to_owned()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
     } else {
-        false
+        "Panicked without a message".to_owned()
     }
 }
 
+fn is_unimplemented(message: &str) -> bool {
+    // `todo!()` and `unimplemented!()` differ only in their messages.
+    ["not yet implemented", "not implemented"]
+        .into_iter()
+        .any(|prefix| {
+            message == prefix
+                || message
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with(": "))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn catching_todo_on_no_panic() {
-        let result = catching_todo(|| 42);
-        assert_eq!(result, Some(42));
+    fn catching_panics_on_no_panic() {
+        let result = catching_panics(|| 42);
+        assert_eq!(result, Ok(42));
+    }
+
+    #[test]
+    fn catching_panics_catches_todo() {
+        let result = catching_panics(|| todo!());
+        assert_eq!(result, Err::<(), _>(Panic::Unimplemented));
+    }
+
+    #[test]
+    fn catching_panics_catches_todo_with_message() {
+        let result = catching_panics(|| todo!("stuff"));
+        assert_eq!(result, Err::<(), _>(Panic::Unimplemented));
     }
 
     #[test]
-    fn catching_todo_catches_todo() {
-        let result = catching_todo(|| todo!());
-        assert_eq!(result, None);
+    fn catching_panics_catches_unimplemented() {
+        assert_eq!(
+            catching_panics(|| unimplemented!()),
+            Err::<(), _>(Panic::Unimplemented)
+        );
+        assert_eq!(
+            catching_panics(|| unimplemented!("stuff")),
+            Err::<(), _>(Panic::Unimplemented)
+        );
     }
 
     #[test]
-    fn catching_todo_catches_todo_with_message() {
-        let result = catching_todo(|| todo!("stuff"));
-        assert_eq!(result, None);
+    fn catching_panics_catches_other_panics() {
+        let line = line!() + 1;
+        let panic = catching_panics(|| panic!("not implemented yet")).unwrap_err();
+        let Panic::Other { message, location } = &panic else {
+            panic!("Expected another panic, got {:?}", panic);
+        };
+        assert_eq!(message, "not implemented yet");
+        let at = format!("{}:{}:", file!(), line);
+        assert!(location.as_ref().unwrap().starts_with(&at));
+        assert!(panic
+            .to_string()
+            .starts_with(&format!("Panicked at {}", at)));
+        assert!(panic.to_string().ends_with(": not implemented yet"));
+        let panic = catching_panics(|| -> u32 { "x".parse().unwrap() }).unwrap_err();
+        assert!(panic.to_string().ends_with(
+            ": called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }"
+        ));
     }
 
     #[test]
-    #[should_panic]
-    fn catching_todo_allows_other_panics() {
-        catching_todo(|| unimplemented!());
+    fn catching_panics_on_many_threads() {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    (0..100)
+                        .map(|j| {
+                            catching_panics(move || if (i + j) % 2 == 0 { todo!() } else { j })
+                        })
+                        .filter(Result::is_err)
+                        .count()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 50);
+        }
     }
 }
@@ -0,0 +1,648 @@
+use crate::harness::cli::{Args, Format};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+/// What happened when a single part was run against its inputs.
+#[derive(Clone, Debug)]
+pub struct PartReport {
+    pub day: usize,
+    pub title: &'static str,
+    pub part: usize,
+    /// Whether the answer is ASCII art printed by the solver, so that the value
+    /// it returned is meaningless.
+    pub ascii_art: bool,
+    /// One report per test input with an expected output for this part.
+    pub tests: Vec<TestReport>,
+    /// `None` if the puzzle input was skipped, or if the test failed.
+    pub solution: Option<Solution>,
+    /// Set if the part hit a `todo!()` or `unimplemented!()`, or the puzzle has
+    /// no such part.
+    pub not_implemented: bool,
+    /// Set if the solver returned an error. Nothing after the failing input is
+    /// run.
+    pub error: Option<SolverError>,
+    /// Set if the part was stopped for running past `--timeout` or
+    /// `--memory-limit`. Nothing else is known about it.
+    pub limit_exceeded: Option<LimitExceeded>,
+}
+
+/// How a part ended. Every output format shows this.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Status {
+    /// Nothing failed. This includes parts with nothing to check.
+    Passed,
+    /// A test output or the answer was wrong.
+    Failed,
+    Unimplemented,
+    /// The solver returned an error, or its process crashed.
+    Errored,
+    /// The part was stopped by `--timeout` or `--memory-limit`.
+    TimedOut,
+}
+
+impl Status {
+    pub const ALL: [Status; 5] = [
+        Status::Passed,
+        Status::Failed,
+        Status::Unimplemented,
+        Status::Errored,
+        Status::TimedOut,
+    ];
+
+    /// A short label for tables.
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Passed => "PASS",
+            Status::Failed => "FAIL",
+            Status::Unimplemented => "TODO",
+            Status::Errored => "ERROR",
+            Status::TimedOut => "TIMEOUT",
+        }
+    }
+
+    /// The name used in JSON, and in summaries such as "2 failed".
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Passed => "passed",
+            Status::Failed => "failed",
+            Status::Unimplemented => "unimplemented",
+            Status::Errored => "errored",
+            Status::TimedOut => "timed_out",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TestReport {
+    /// The `<name>` in `dayN-test-<name>.txt`.
+    pub name: String,
+    pub output: String,
+    pub expected: String,
+    /// Outputs are compared before being formatted, so this is not necessarily
+    /// the same as `output == expected`.
+    pub passed: bool,
+    /// What the solver printed while parsing and solving this input.
+    pub printed: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub answer: String,
+    pub duration: Duration,
+    /// How long it took to parse the input, which is shared by both parts.
+    /// `None` for days that parse the input in each part.
+    pub parse_duration: Option<Duration>,
+    /// What the solver printed while parsing and solving the puzzle input.
+    pub printed: String,
+    /// The previously accepted answer for this input, if one was recorded.
+    pub recorded: Option<RecordedAnswer>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SolverError {
+    /// The name of the test input that caused the error, or `None` for the
+    /// puzzle input.
+    pub test_input: Option<String>,
+    pub message: String,
+}
+
+impl SolverError {
+    pub fn new(test_input: Option<&str>, error: impl Display) -> Self {
+        Self {
+            test_input: test_input.map(str::to_owned),
+            message: error.to_string(),
+        }
+    }
+
+    /// Describes the error along with the input that caused it.
+    pub fn describe(&self) -> String {
+        match &self.test_input {
+            Some(name) => format!("Test input {}: {}", name, self.message),
+            None => format!("Puzzle input: {}", self.message),
+        }
+    }
+}
+
+/// A limit that a part was stopped for exceeding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LimitExceeded {
+    Time(Duration),
+    /// In megabytes.
+    Memory(u64),
+}
+
+impl Display for LimitExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::Time(limit) => write!(f, "time limit of {:?}", limit),
+            LimitExceeded::Memory(megabytes) => write!(f, "memory limit of {} MB", megabytes),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RecordedAnswer {
+    pub answer: String,
+    pub matches: bool,
+}
+
+impl PartReport {
+    pub fn new(day: usize, part: usize) -> Self {
+        Self {
+            day,
+            title: "",
+            part,
+            ascii_art: false,
+            tests: vec![],
+            solution: None,
+            not_implemented: false,
+            error: None,
+            limit_exceeded: None,
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        let test_failed = self.tests.iter().any(|test| !test.passed);
+        let answer_changed = self
+            .solution
+            .as_ref()
+            .and_then(|solution| solution.recorded.as_ref())
+            .is_some_and(|recorded| !recorded.matches);
+        if self.limit_exceeded.is_some() {
+            Status::TimedOut
+        } else if self.error.is_some() {
+            Status::Errored
+        } else if test_failed || answer_changed {
+            Status::Failed
+        } else if self.not_implemented {
+            Status::Unimplemented
+        } else {
+            Status::Passed
+        }
+    }
+
+    /// Whether there's any point running more inputs. Stops at a `todo!()` or
+    /// an error, but not at a wrong answer, so that every test is reported.
+    pub fn is_running(&self) -> bool {
+        !self.not_implemented && self.error.is_none()
+    }
+
+    /// Whether either the test output or the answer was wrong, or the solver
+    /// returned an error or was stopped by a limit.
+    pub fn failed(&self) -> bool {
+        matches!(
+            self.status(),
+            Status::Failed | Status::Errored | Status::TimedOut
+        )
+    }
+}
+
+/// Prints reports in the format chosen by `args`.
+pub fn print(reports: &[PartReport], args: &Args, wall_time: Duration) {
+    match args.format {
+        Format::Text if args.all => print_table(reports, wall_time),
+        Format::Text => {
+            for (i, report) in reports.iter().enumerate() {
+                if i == 0 {
+                    println!("--- Day {}: {} ---", report.day, report.title);
+                } else {
+                    println!();
+                }
+                print_part(report);
+            }
+        }
+        Format::Json => {
+            let records: Vec<_> = reports.iter().map(to_json).collect();
+            println!("{}", serde_json::to_string_pretty(&records).unwrap());
+        }
+        Format::Junit => print_junit(reports, wall_time),
+    }
+}
+
+/// Prints a report in the same style as when parts are run one at a time.
+pub fn print_part(report: &PartReport) {
+    let part = report.part;
+    for test in &report.tests {
+        // Only name the test input when there's more than one.
+        let label = if report.tests.len() > 1 {
+            format!("Part {} test output ({})", part, test.name)
+        } else {
+            format!("Part {} test output", part)
+        };
+        if test.passed {
+            print_value(&label, &test.output, "✅");
+        } else {
+            print_value(&label, &test.output, "❌");
+            let expected_label = format!("{:>width$}", "Expected", width = label.len());
+            print_value(&expected_label, &test.expected, "");
+        }
+        print_printed(
+            &format!("Part {} printed ({})", part, test.name),
+            &test.printed,
+        );
+    }
+    match (report.status(), &report.error, &report.limit_exceeded) {
+        (Status::Unimplemented, _, _) => println!("Part {} not implemented.", part),
+        (Status::TimedOut, _, Some(limit)) => {
+            println!("Part {} TIMEOUT: exceeded the {} ❌", part, limit)
+        }
+        (Status::Errored, Some(error), _) => match &error.test_input {
+            Some(name) => println!("Part {} test error ({}): {} ❌", part, name, error.message),
+            None => println!("Part {} error: {} ❌", part, error.message),
+        },
+        _ => {}
+    }
+    if let Some(solution) = &report.solution {
+        let label = format!("Part {} output", part);
+        match &solution.recorded {
+            Some(recorded) if recorded.matches => print_value(&label, &solution.answer, "✅"),
+            Some(recorded) => {
+                print_value(&label, &solution.answer, "❌");
+                print_value("     Expected", &recorded.answer, "");
+            }
+            None => print_value(&label, &solution.answer, ""),
+        }
+        match solution.parse_duration {
+            Some(parse_duration) => println!(
+                "   ↑ Duration: {:.2?} (plus {:.2?} parsing)",
+                solution.duration, parse_duration
+            ),
+            None => println!("   ↑ Duration: {:.2?}", solution.duration),
+        }
+        print_printed(&format!("Part {} printed", part), &solution.printed);
+    }
+}
+
+/// Prints `label: value`, followed by `mark` if it isn't empty. A value with
+/// several lines starts on the line after the label, indented.
+fn print_value(label: &str, value: &str, mark: &str) {
+    if value.contains('\n') {
+        if mark.is_empty() {
+            println!("{}:", label);
+        } else {
+            println!("{}: {}", label, mark);
+        }
+        for line in value.lines() {
+            println!("    {}", line);
+        }
+    } else if mark.is_empty() {
+        println!("{}: {}", label, value);
+    } else {
+        println!("{}: {} {}", label, value, mark);
+    }
+}
+
+/// Prints what a solver printed, if anything, set off from the results.
+fn print_printed(label: &str, printed: &str) {
+    if printed.is_empty() {
+        return;
+    }
+    println!("{}:", label);
+    for line in printed.lines() {
+        println!("  │ {}", line);
+    }
+}
+
+/// Prints one row per part, followed by the total time spent solving and the
+/// wall-clock time of the whole run.
+pub fn print_table(reports: &[PartReport], wall_time: Duration) {
+    let header = [
+        "Day", "Part", "Status", "Answer", "Test", "Recorded", "Parse", "Duration",
+    ]
+    .map(String::from);
+    let rows: Vec<[String; 8]> = reports
+        .iter()
+        .map(|report| {
+            [
+                report.day.to_string(),
+                report.part.to_string(),
+                report.status().label().to_owned(),
+                match (&report.solution, &report.error) {
+                    (Some(_), _) if report.ascii_art => "(ASCII art)".to_owned(),
+                    (Some(solution), _) if solution.answer.contains('\n') => {
+                        format!("({} lines)", solution.answer.lines().count())
+                    }
+                    (Some(solution), _) => solution.answer.clone(),
+                    (None, Some(error)) if error.test_input.is_none() => {
+                        format!("error: {}", error.message)
+                    }
+                    (None, _) => "-".to_owned(),
+                },
+                test_status(report),
+                recorded_status(report).to_owned(),
+                match report.solution.as_ref().and_then(|s| s.parse_duration) {
+                    Some(parse_duration) => format!("{:.2?}", parse_duration),
+                    None => "-".to_owned(),
+                },
+                match &report.solution {
+                    Some(solution) => format!("{:.2?}", solution.duration),
+                    None => "-".to_owned(),
+                },
+            ]
+        })
+        .collect();
+    let mut widths = header.clone().map(|cell| cell.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let print_row = |row: &[String; 8]| {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    };
+    print_row(&header);
+    println!(
+        "{}",
+        "-".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1))
+    );
+    for row in &rows {
+        print_row(row);
+    }
+    // Both parts of a day share one parse, so only count it once.
+    let mut parse_durations = BTreeMap::new();
+    for report in reports {
+        if let Some(parse_duration) = report.solution.as_ref().and_then(|s| s.parse_duration) {
+            parse_durations.insert(report.day, parse_duration);
+        }
+    }
+    let total: Duration = reports
+        .iter()
+        .filter_map(|report| report.solution.as_ref())
+        .map(|solution| solution.duration)
+        .chain(parse_durations.into_values())
+        .sum();
+    println!();
+    println!(
+        "Total duration: {:.2?} (wall time {:.2?})",
+        total, wall_time
+    );
+    let counts: Vec<_> = Status::ALL
+        .into_iter()
+        .map(|status| {
+            let count = reports
+                .iter()
+                .filter(|report| report.status() == status)
+                .count();
+            (status, count)
+        })
+        .filter(|&(status, count)| status != Status::Passed && count > 0)
+        .map(|(status, count)| format!("{} {}", count, status.name().replace('_', " ")))
+        .collect();
+    if !counts.is_empty() {
+        println!("{}.", counts.join(", "));
+    }
+}
+
+fn test_status(report: &PartReport) -> String {
+    if report
+        .error
+        .as_ref()
+        .is_some_and(|error| error.test_input.is_some())
+    {
+        return "ERROR".to_owned();
+    }
+    let total = report.tests.len();
+    let passed = report.tests.iter().filter(|test| test.passed).count();
+    let status = match (total, passed == total) {
+        (0, _) => return "-".to_owned(),
+        (_, true) => "pass",
+        (_, false) => "FAIL",
+    };
+    if total == 1 {
+        status.to_owned()
+    } else {
+        format!("{} {}/{}", status, passed, total)
+    }
+}
+
+fn recorded_status(report: &PartReport) -> &'static str {
+    match report
+        .solution
+        .as_ref()
+        .and_then(|solution| solution.recorded.as_ref())
+    {
+        Some(recorded) if recorded.matches => "pass",
+        Some(_) => "FAIL",
+        None => "-",
+    }
+}
+
+pub fn to_json(report: &PartReport) -> Value {
+    let tests: Vec<_> = report
+        .tests
+        .iter()
+        .map(|test| {
+            json!({
+                "name": test.name,
+                "actual": test.output,
+                "expected": test.expected,
+                "passed": test.passed,
+                "printed": test.printed,
+            })
+        })
+        .collect();
+    let solution = report.solution.as_ref();
+    let recorded = solution.and_then(|solution| solution.recorded.as_ref());
+    json!({
+        "day": report.day,
+        "title": report.title,
+        "part": report.part,
+        "ascii_art": report.ascii_art,
+        "status": report.status().name(),
+        "not_implemented": report.not_implemented,
+        "failed": report.failed(),
+        "error": report.error.as_ref().map(|error| &error.message),
+        "error_input": report.error.as_ref().map(|error| &error.test_input),
+        "timed_out": report.limit_exceeded.is_some(),
+        "limit_exceeded": report.limit_exceeded.map(|limit| limit.to_string()),
+        "tests": tests,
+        "answer": solution.map(|solution| &solution.answer),
+        "printed": solution.map(|solution| &solution.printed),
+        "duration_ns": solution.map(|solution| solution.duration.as_nanos() as u64),
+        "parse_duration_ns": solution
+            .and_then(|solution| solution.parse_duration)
+            .map(|duration| duration.as_nanos() as u64),
+        "recorded_answer": recorded.map(|recorded| &recorded.answer),
+        "matches_recorded": recorded.map(|recorded| recorded.matches),
+    })
+}
+
+/// Reads a report written by `to_json`, as when a part is run in a child
+/// process. Returns `None` if a field is missing.
+pub fn from_json(value: &Value, title: &'static str) -> Option<PartReport> {
+    let string = |value: &Value, key: &str| value[key].as_str().map(str::to_owned);
+    let tests = value["tests"]
+        .as_array()?
+        .iter()
+        .map(|test| {
+            Some(TestReport {
+                name: string(test, "name")?,
+                output: string(test, "actual")?,
+                expected: string(test, "expected")?,
+                passed: test["passed"].as_bool()?,
+                printed: string(test, "printed")?,
+            })
+        })
+        .collect::<Option<_>>()?;
+    let solution = match string(value, "answer") {
+        Some(answer) => Some(Solution {
+            answer,
+            duration: Duration::from_nanos(value["duration_ns"].as_u64()?),
+            parse_duration: value["parse_duration_ns"]
+                .as_u64()
+                .map(Duration::from_nanos),
+            printed: string(value, "printed")?,
+            recorded: match string(value, "recorded_answer") {
+                Some(answer) => Some(RecordedAnswer {
+                    answer,
+                    matches: value["matches_recorded"].as_bool()?,
+                }),
+                None => None,
+            },
+        }),
+        None => None,
+    };
+    Some(PartReport {
+        day: value["day"].as_u64()? as usize,
+        title,
+        part: value["part"].as_u64()? as usize,
+        ascii_art: value["ascii_art"].as_bool()?,
+        tests,
+        solution,
+        not_implemented: value["not_implemented"].as_bool()?,
+        error: string(value, "error").map(|message| SolverError {
+            test_input: string(value, "error_input"),
+            message,
+        }),
+        limit_exceeded: None,
+    })
+}
+
+/// Prints a JUnit XML report, with one test case per day and part. A failed
+/// test input or a changed answer counts as a failure, a solver that returned
+/// an error or was stopped by a limit counts as an error, and a part that hit a
+/// `todo!()` is skipped.
+pub fn print_junit(reports: &[PartReport], wall_time: Duration) {
+    let count = |statuses: &[Status]| {
+        reports
+            .iter()
+            .filter(|report| statuses.contains(&report.status()))
+            .count()
+    };
+    let failures = count(&[Status::Failed]);
+    let errors = count(&[Status::Errored, Status::TimedOut]);
+    let skipped = count(&[Status::Unimplemented]);
+    println!(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    println!(
+        r#"<testsuite name="advent-of-code-2022" tests="{}" failures="{}" errors="{}" skipped="{}" time="{:.6}">"#,
+        reports.len(),
+        failures,
+        errors,
+        skipped,
+        wall_time.as_secs_f64()
+    );
+    for report in reports {
+        let time = report
+            .solution
+            .as_ref()
+            .map_or(0.0, |solution| solution.duration.as_secs_f64());
+        println!(
+            r#"  <testcase classname="day{}" name="part{}" time="{:.6}">"#,
+            report.day, report.part, time
+        );
+        for test in report.tests.iter().filter(|test| !test.passed) {
+            let message = format!(
+                "Test input {}: expected {}, got {}",
+                test.name, test.expected, test.output
+            );
+            println!(r#"    <failure message="{}"/>"#, escape_xml(&message));
+        }
+        if let Some(solution) = &report.solution {
+            if let Some(recorded) = solution.recorded.as_ref().filter(|r| !r.matches) {
+                let message = format!(
+                    "Answer changed: expected {}, got {}",
+                    recorded.answer, solution.answer
+                );
+                println!(r#"    <failure message="{}"/>"#, escape_xml(&message));
+            }
+            println!(
+                "    <system-out>{}</system-out>",
+                escape_xml(&(solution.answer.clone() + "\n" + &solution.printed))
+            );
+        }
+        if let Some(error) = &report.error {
+            println!(
+                r#"    <error message="{}"/>"#,
+                escape_xml(&error.describe())
+            );
+        }
+        if let Some(limit) = &report.limit_exceeded {
+            let message = format!("TIMEOUT: exceeded the {}", limit);
+            println!(r#"    <error message="{}"/>"#, escape_xml(&message));
+        }
+        if report.status() == Status::Unimplemented {
+            println!(r#"    <skipped message="not implemented"/>"#);
+        }
+        println!("  </testcase>");
+    }
+    println!("</testsuite>");
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"<a href="x">&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&apos;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut report = PartReport::new(3, 2);
+        report.tests.push(TestReport {
+            name: "1".to_owned(),
+            output: "70".to_owned(),
+            expected: "70".to_owned(),
+            passed: true,
+            printed: String::new(),
+        });
+        report.solution = Some(Solution {
+            answer: "2758".to_owned(),
+            duration: Duration::from_micros(150),
+            parse_duration: None,
+            printed: "Checked 300 rucksacks.\n".to_owned(),
+            recorded: Some(RecordedAnswer {
+                answer: "2757".to_owned(),
+                matches: false,
+            }),
+        });
+        let read = from_json(&to_json(&report), "").unwrap();
+        assert_eq!(to_json(&read), to_json(&report));
+    }
+}
@@ -0,0 +1,156 @@
+//! The `new-day` subcommand, which creates the files for a new day.
+
+use crate::harness::cli::Exit;
+use regex::Regex;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// The module for a new day, with `$DAY` and `$TITLE` to be filled in.
+const TEMPLATE: &str = include_str!("day_template.txt");
+
+/// The header of a test input, for the expected outputs to be filled in.
+const TEST_INPUT_HEADER: &str = "Part 1 expected:\nPart 2 expected:\n\n";
+
+/// Creates `src/days/dayN.rs` and the input files for `day`, and registers the
+/// day in `src/days.rs`. Paths are relative to the crate, wherever this is run
+/// from. Nothing is written if any of the files already exist.
+pub fn new_day(day: usize, title: Option<&str>, input_dir: &Path) -> Exit {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let days_path = crate_dir.join("src/days.rs");
+    let module_path = crate_dir.join(format!("src/days/day{}.rs", day));
+    let input_dir = crate_dir.join(input_dir);
+    let input_path = input_dir.join(format!("day{}-input.txt", day));
+    let test_path = input_dir.join(format!("day{}-test-input.txt", day));
+    let paths = [&module_path, &input_path, &test_path];
+    // Checked up front to report every existing file at once. `create_new`
+    // still refuses to overwrite a file that appears after this.
+    let existing: Vec<_> = paths.iter().filter(|path| path.exists()).collect();
+    if !existing.is_empty() {
+        for path in existing {
+            eprintln!("{} already exists.", path.display());
+        }
+        return Exit::Usage;
+    }
+    let days = match fs::read_to_string(&days_path) {
+        Ok(days) => days,
+        Err(error) => {
+            eprintln!("Could not read {}: {}", days_path.display(), error);
+            return Exit::BadInput;
+        }
+    };
+    let days = match register(&days, day) {
+        Ok(days) => days,
+        Err(error) => {
+            eprintln!(
+                "Could not register day {} in {}: {}",
+                day,
+                days_path.display(),
+                error
+            );
+            return Exit::Usage;
+        }
+    };
+    let module = TEMPLATE
+        .replace("$DAY", &day.to_string())
+        .replace("$TITLE", &format!("{:?}", title.unwrap_or("TODO")));
+    let contents = [module.as_str(), "", TEST_INPUT_HEADER];
+    // `src/days.rs` is written last, so that a day is never registered without
+    // its module. Files created before a failure are removed again.
+    let mut created = vec![];
+    for (path, contents) in paths.into_iter().zip(contents) {
+        if let Err(error) = create_new(path, contents) {
+            eprintln!("Could not create {}: {}", path.display(), error);
+            remove_all(&created);
+            return Exit::BadInput;
+        }
+        created.push(path);
+    }
+    if let Err(error) = fs::write(&days_path, days) {
+        eprintln!("Could not write {}: {}", days_path.display(), error);
+        remove_all(&created);
+        return Exit::BadInput;
+    }
+    for path in created {
+        println!("Created {}", path.display());
+    }
+    println!(
+        "Registered day{}::Day{} in {}",
+        day,
+        day,
+        days_path.display()
+    );
+    Exit::Success
+}
+
+fn remove_all(paths: &[&PathBuf]) {
+    for path in paths {
+        if let Err(error) = fs::remove_file(path) {
+            eprintln!("Could not remove {}: {}", path.display(), error);
+        }
+    }
+}
+
+fn create_new(path: &Path, contents: &str) -> io::Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Adds `day` to the list in `with_days!`, keeping it in order.
+fn register(days: &str, day: usize) -> Result<String, String> {
+    let re = Regex::new(r"^( *)day(\d+)::Day\d+(?:\(.*\))?,$").unwrap();
+    let entries: Vec<_> = days
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let caps = re.captures(line)?;
+            Some((i, caps[1].to_owned(), caps[2].parse::<usize>().ok()?))
+        })
+        .collect();
+    let Some((_, indent, _)) = entries.first() else {
+        return Err("could not find the list of days".to_owned());
+    };
+    if entries.iter().any(|&(_, _, registered)| registered == day) {
+        return Err("it is already registered".to_owned());
+    }
+    let index = match entries
+        .iter()
+        .rev()
+        .find(|&&(_, _, registered)| registered < day)
+    {
+        Some(&(i, _, _)) => i + 1,
+        None => entries[0].0,
+    };
+    let mut lines: Vec<_> = days.lines().collect();
+    let entry = format!("{}day{}::Day{},", indent, day, day);
+    lines.insert(index, &entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let days = "with! {\n    day1::Day1,\n    day3::Day3,\n}\n";
+        assert_eq!(
+            register(days, 2).unwrap(),
+            "with! {\n    day1::Day1,\n    day2::Day2,\n    day3::Day3,\n}\n"
+        );
+        assert_eq!(
+            register(days, 4).unwrap(),
+            "with! {\n    day1::Day1,\n    day3::Day3,\n    day4::Day4,\n}\n"
+        );
+        assert!(register(days, 3).is_err());
+        let days = "with! {\n    day1::Day1(solve_part1, solve_part2),\n}\n";
+        assert_eq!(
+            register(days, 2).unwrap(),
+            "with! {\n    day1::Day1(solve_part1, solve_part2),\n    day2::Day2,\n}\n"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::harness::input::RawInput;
+use crate::regex;
+use std::path::{Path, PathBuf};
+use std::{error, fs, io};
+
+/// A test input file, as read from disk. A day may have several, named
+/// `dayN-test-<name>.txt`, where the original example is `dayN-test-input.txt`.
+#[derive(Clone, Debug)]
+pub struct TestFile {
+    pub name: String,
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Reads every test input file for a day from `dir`. The file named `input`
+/// comes first, followed by the rest in alphabetical order.
+pub fn read_test_files(dir: &Path, day: usize) -> io::Result<Vec<TestFile>> {
+    let prefix = format!("day{}-test-", day);
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let name = file_name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".txt"));
+        if let Some(name) = name {
+            files.push(TestFile {
+                name: name.to_owned(),
+                contents: fs::read_to_string(&path)?,
+                path,
+            });
+        }
+    }
+    files.sort_by(|a, b| (a.name != "input", &a.name).cmp(&(b.name != "input", &b.name)));
+    Ok(files)
+}
+
+/// A parsed test input. Expected outputs are left unparsed until the solver
+/// for the corresponding part tells us what type to parse them as.
+#[derive(Copy, Clone, Debug)]
+pub struct TestInput<'a> {
+    pub name: &'a str,
+    /// The file the test input was read from, or `None` if it was written in
+    /// code.
+    pub path: Option<&'a Path>,
+    pub part1_expected: Option<&'a str>,
+    pub part2_expected: Option<&'a str>,
+    pub text: &'a str,
+    /// The line of the file that `text` starts on, after the header.
+    pub first_line: usize,
+}
+
+impl<'a> TestInput<'a> {
+    pub fn parse(file: &'a TestFile) -> Result<Self, Box<dyn error::Error>> {
+        // An expected output is either the rest of its line, or, for answers
+        // with several lines, the lines between two lines of `"""`.
+        let re = regex!(
+            r#"(?s)^Part 1 expected: *(?:"""\n(.*?)\n"""|([^\n]+))?
+Part 2 expected: *(?:"""\n(.*?)\n"""|([^\n]+))?
+ *
+(.*)$"#
+        );
+        let caps = re
+            .captures(&file.contents)
+            .ok_or_else(|| format!("Invalid test input format in {}.", file.name))?;
+        let expected = |part: usize| {
+            let group = 2 * part - 1;
+            caps.get(group)
+                .or_else(|| caps.get(group + 1))
+                .map(|m| m.as_str())
+        };
+        let text = caps.get(5).unwrap();
+        Ok(TestInput {
+            name: &file.name,
+            path: Some(&file.path),
+            part1_expected: expected(1),
+            part2_expected: expected(2),
+            text: text.as_str(),
+            first_line: file.contents[..text.start()].matches('\n').count() + 1,
+        })
+    }
+
+    /// A test input written in code, for `Day::TEST_INPUTS`.
+    pub const fn inline(
+        name: &'a str,
+        text: &'a str,
+        part1_expected: Option<&'a str>,
+        part2_expected: Option<&'a str>,
+    ) -> Self {
+        Self {
+            name,
+            path: None,
+            part1_expected,
+            part2_expected,
+            text,
+            first_line: 1,
+        }
+    }
+
+    /// The text to run a solver on, which reports parse errors against the
+    /// lines of the test file.
+    pub fn raw_input(&self) -> RawInput<'a> {
+        match self.path {
+            Some(path) => RawInput::new(self.text).with_source(path, self.first_line),
+            None => RawInput::new(self.text),
+        }
+    }
+
+    pub fn expected(&self, part: usize) -> Option<&'a str> {
+        match part {
+            1 => self.part1_expected,
+            _ => self.part2_expected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_file(name: &str, contents: &str) -> TestFile {
+        TestFile {
+            name: name.to_owned(),
+            path: PathBuf::from(format!("day1-test-{}.txt", name)),
+            contents: contents.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_parse_test_input() {
+        let file = test_file("input", "Part 1 expected: 24\nPart 2 expected:\n\n1\n2\n");
+        let input = TestInput::parse(&file).unwrap();
+        assert_eq!(input.expected(1), Some("24"));
+        assert_eq!(input.expected(2), None);
+        assert_eq!(input.text, "1\n2\n");
+        assert_eq!(input.first_line, 4);
+    }
+
+    #[test]
+    fn test_parse_multiline_expected() {
+        let file = test_file(
+            "input",
+            "Part 1 expected: 7\nPart 2 expected: \"\"\"\n#.#\n .#\n\"\"\"\n\n1\n",
+        );
+        let input = TestInput::parse(&file).unwrap();
+        assert_eq!(input.expected(1), Some("7"));
+        assert_eq!(input.expected(2), Some("#.#\n .#"));
+        assert_eq!(input.text, "1\n");
+        assert_eq!(input.first_line, 7);
+    }
+
+    #[test]
+    fn test_parse_invalid_test_input() {
+        let file = test_file("edge", "Part 1: 24\n\n1\n");
+        assert!(TestInput::parse(&file).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! `--watch`: reruns a day whenever its inputs or the code change. Each run
+//! happens in a child process, so that after a rebuild the new code is run.
+
+use crate::harness::cli::{Args, Exit};
+use crate::harness::report::{self, PartReport, Status};
+use crate::harness::{child, RegisteredDay};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime};
+use std::{fs, io, thread};
+
+/// How often to check for changed files.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// How long to wait after a change before rerunning, since editors often
+/// write a file several times when saving it.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// The modification time of each watched file.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Runs `registered` and then reruns it after every change, until interrupted.
+pub fn run(registered: &RegisteredDay, args: &Args) -> Exit {
+    let code_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut snapshot = take_snapshot(registered.day, args, code_dir);
+    let mut previous: Option<Vec<PartReport>> = None;
+    loop {
+        let start_time = Instant::now();
+        // Problems with the input files have already been printed, and may be
+        // fixed by the next change.
+        let reports = child::solve(registered, args).unwrap_or_default();
+        report::print(&reports, args, start_time.elapsed());
+        if let Some(previous) = &previous {
+            print_changes(previous, &reports);
+        }
+        previous = Some(reports);
+        println!();
+        println!(
+            "Watching {}/day{}-* and {} for changes.",
+            args.input_dir().display(),
+            registered.day,
+            code_dir.join("src").display()
+        );
+        loop {
+            let changed = wait_for_change(&mut snapshot, registered.day, args, code_dir);
+            println!();
+            if !changed.iter().any(|path| path.starts_with(code_dir)) {
+                break;
+            }
+            println!("Rebuilding...");
+            match rebuild(code_dir) {
+                Ok(true) => break,
+                Ok(false) => println!("Build failed. Waiting for the next change."),
+                Err(error) => println!("Could not run cargo: {}", error),
+            }
+        }
+    }
+}
+
+/// Blocks until a watched file changes, and returns the files that changed.
+fn wait_for_change(
+    snapshot: &mut Snapshot,
+    day: usize,
+    args: &Args,
+    code_dir: &Path,
+) -> Vec<PathBuf> {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        if take_snapshot(day, args, code_dir) == *snapshot {
+            continue;
+        }
+        thread::sleep(SETTLE_TIME);
+        let latest = take_snapshot(day, args, code_dir);
+        let changed = latest
+            .iter()
+            .filter(|&(path, time)| snapshot.get(path) != Some(time))
+            .map(|(path, _)| path.clone())
+            .chain(
+                snapshot
+                    .keys()
+                    .filter(|path| !latest.contains_key(*path))
+                    .cloned(),
+            )
+            .collect();
+        *snapshot = latest;
+        return changed;
+    }
+}
+
+/// Finds the files that `day` depends on: its input files, the recorded
+/// answers, and the source code.
+fn take_snapshot(day: usize, args: &Args, code_dir: &Path) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let prefix = format!("day{}-", day);
+    let input_dir = args.input_dir();
+    if let Ok(entries) = fs::read_dir(input_dir) {
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                add_file(&mut snapshot, &entry.path());
+            }
+        }
+    }
+    add_file(&mut snapshot, &input_dir.join("answers.json"));
+    if let Some(input) = &args.input {
+        add_file(&mut snapshot, input);
+    }
+    add_file(&mut snapshot, &code_dir.join("Cargo.toml"));
+    add_dir(&mut snapshot, &code_dir.join("src"));
+    snapshot
+}
+
+fn add_dir(snapshot: &mut Snapshot, dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            add_dir(snapshot, &path);
+        } else {
+            add_file(snapshot, &path);
+        }
+    }
+}
+
+fn add_file(snapshot: &mut Snapshot, path: &Path) {
+    if let Ok(modified) = fs::metadata(path).and_then(|metadata| metadata.modified()) {
+        snapshot.insert(path.to_owned(), modified);
+    }
+}
+
+/// Rebuilds this binary with the profile it was built with. Returns whether the
+/// build succeeded.
+fn rebuild(code_dir: &Path) -> io::Result<bool> {
+    let mut command = Command::new(option_env!("CARGO").unwrap_or("cargo"));
+    command
+        .args(["build", "--quiet", "--manifest-path"])
+        .arg(code_dir.join("Cargo.toml"));
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    Ok(command.status()?.success())
+}
+
+/// Prints how each part's outcome and test outputs differ from the last run.
+fn print_changes(previous: &[PartReport], reports: &[PartReport]) {
+    let mut changes = vec![];
+    for report in reports {
+        let Some(before) = previous.iter().find(|before| before.part == report.part) else {
+            continue;
+        };
+        let part = report.part;
+        for test in &report.tests {
+            let output = before
+                .tests
+                .iter()
+                .find(|before| before.name == test.name)
+                .map(|before| &before.output)
+                .filter(|&output| *output != test.output);
+            if let Some(output) = output {
+                changes.push(format!(
+                    "Part {} test output ({}): {} → {}",
+                    part, test.name, output, test.output
+                ));
+            }
+        }
+        let (before, after) = (outcome(before), outcome(report));
+        if before != after {
+            changes.push(format!("Part {}: {} → {}", part, before, after));
+        }
+    }
+    println!();
+    if changes.is_empty() {
+        println!("No changes since the last run.");
+    } else {
+        println!("Changes since the last run:");
+        for change in changes {
+            println!("  {}", change);
+        }
+    }
+}
+
+/// A short description of how a part ended, to compare between runs.
+fn outcome(report: &PartReport) -> String {
+    match (report.status(), &report.error, &report.solution) {
+        (Status::Errored, Some(error), _) => format!("error: {}", error.message),
+        (Status::Passed | Status::Failed, _, Some(solution)) => solution.answer.clone(),
+        (Status::Passed | Status::Failed, _, None) => "-".to_owned(),
+        (status, _, _) => status.label().to_owned(),
+    }
+}
@@ -0,0 +1,5 @@
+#![allow(dead_code)]
+
+pub mod days;
+pub mod harness;
+pub mod util;
@@ -1,12 +1,8 @@
-#![allow(dead_code)]
-extern crate core;
+use advent_of_code_2022::harness;
+use advent_of_code_2022::harness::cli::Args;
+use clap::Parser;
+use std::process::ExitCode;
 
-mod days;
-mod harness;
-mod util;
-
-use crate::days::{day, DAY};
-
-fn main() {
-    harness::solve(DAY, day::solve_part1, day::solve_part2);
+fn main() -> ExitCode {
+    harness::run(&Args::parse()).into()
 }
@@ -1,6 +1,8 @@
 pub mod coords;
 pub mod crt;
 pub mod grid;
+pub mod ocr;
+pub mod pattern;
 pub mod re;
 pub mod search;
 pub mod string_enum;
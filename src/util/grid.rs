@@ -1,6 +1,7 @@
 use crate::harness::input::{LineInput, RawInput};
 use crate::util::coords::Coord2;
 use ndarray::Array2;
+use std::error;
 use std::fmt::Debug;
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
@@ -29,7 +30,7 @@ impl Grid<u8> {
 impl<T> Grid<T>
 where
     T: Default + FromStr,
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Into<Box<dyn error::Error>>,
 {
     pub fn parse_on_whitespace(s: &str) -> Self {
         parse_grid(s, |line| line.split_whitespace())
@@ -123,7 +124,7 @@ mod tests {
     #[test]
     fn test_neighbors() {
         let grid = Grid(Array2::<i32>::zeros((4, 4)));
-        let neighbors: HashSet<_> = grid.neighbors([1, 2]).into_iter().collect();
+        let neighbors: HashSet<_> = grid.neighbors([1, 2]).collect();
         let expected: HashSet<_> = [
             [0, 1],
             [1, 1],
@@ -150,7 +151,7 @@ mod tests {
         let expected: HashSet<_> = [[0, 2], [2, 2], [1, 1], [1, 3]].into_iter().collect();
         assert_eq!(neighbors, expected);
 
-        let neighbors: HashSet<_> = grid.orthogonal_neighbors([0, 3]).into_iter().collect();
+        let neighbors: HashSet<_> = grid.orthogonal_neighbors([0, 3]).collect();
         let expected: HashSet<_> = [[1, 3], [0, 2]].into_iter().collect();
         assert_eq!(neighbors, expected);
     }
@@ -0,0 +1,222 @@
+use crate::util::grid::Grid;
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+/// The 6-pixel-tall font used by most puzzles that draw letters, such as 2022
+/// day 10. Most letters are 4 pixels wide, but `I` and `Y` are 5.
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###.\n..#..\n..#..\n..#..\n..#..\n.###."),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The 6x10 font used by 2018 day 10.
+#[rustfmt::skip]
+const FONT_10: &[(char, &str)] = &[
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OcrError {
+    /// The lit pixels don't span the height of either font.
+    UnknownFont { height: usize },
+    /// The letter at `index`, counting from 0, isn't in the font.
+    UnknownLetter { index: usize, pixels: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::UnknownFont { height } => {
+                write!(f, "No font is {} pixels tall.", height)
+            }
+            OcrError::UnknownLetter { index, pixels } => {
+                write!(
+                    f,
+                    "Unrecognized letter at position {}:\n{}",
+                    index + 1,
+                    pixels
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for OcrError {}
+
+/// Reads the letters drawn by the `true` cells of `grid`. Blank rows and
+/// columns around the letters are ignored.
+pub fn read_grid(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = grid.0.rows().into_iter().map(|row| row.to_vec()).collect();
+    read_rows(&rows)
+}
+
+/// Reads the letters drawn in `s`, where `#` and `█` are lit pixels and
+/// anything else is blank. Blank rows and columns around the letters are
+/// ignored.
+pub fn read_str(s: &str) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = s
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+    read_rows(&rows)
+}
+
+fn is_lit(c: char) -> bool {
+    c == '#' || c == '█'
+}
+
+fn read_rows(rows: &[Vec<bool>]) -> Result<String, OcrError> {
+    let lit_rows: Vec<_> = (0..rows.len())
+        .filter(|&i| rows[i].contains(&true))
+        .collect();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Ok(String::new());
+    };
+    let rows = &rows[top..=bottom];
+    let font = match rows.len() {
+        6 => FONT_6,
+        10 => FONT_10,
+        height => return Err(OcrError::UnknownFont { height }),
+    };
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let is_lit_column = |j: usize| rows.iter().any(|row| row.get(j) == Some(&true));
+    let mut letters = String::new();
+    let mut j = 0;
+    while j < width {
+        if !is_lit_column(j) {
+            j += 1;
+            continue;
+        }
+        let start = j;
+        while j < width && is_lit_column(j) {
+            j += 1;
+        }
+        let pixels = draw(rows, start..j);
+        let letter = font
+            .iter()
+            .find(|(_, glyph)| trim_columns(glyph) == pixels)
+            .map(|&(letter, _)| letter)
+            .ok_or_else(|| OcrError::UnknownLetter {
+                index: letters.len(),
+                pixels: pixels.clone(),
+            })?;
+        letters.push(letter);
+    }
+    Ok(letters)
+}
+
+/// Draws the given columns of `rows` with `#` and `.`.
+fn draw(rows: &[Vec<bool>], columns: Range<usize>) -> String {
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            columns
+                .clone()
+                .map(|j| if row.get(j) == Some(&true) { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Removes blank columns from the sides of a glyph, since letters are told
+/// apart by the blank columns between them.
+fn trim_columns(glyph: &str) -> String {
+    let rows: Vec<Vec<bool>> = glyph
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+    let width = rows[0].len();
+    let is_lit_column = |j: usize| rows.iter().any(|row| row[j]);
+    let start = (0..width).find(|&j| is_lit_column(j)).unwrap();
+    let end = (0..width).rfind(|&j| is_lit_column(j)).unwrap() + 1;
+    draw(&rows, start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array2;
+
+    #[test]
+    fn test_read_str() {
+        let s = "\
+####.####.###..###..###..####.####.####.
+#.......#.#..#.#..#.#..#.#.......#.#....
+###....#..###..#..#.###..###....#..###..
+#.....#...#..#.###..#..#.#.....#...#....
+#....#....#..#.#....#..#.#....#....#....
+#....####.###..#....###..#....####.#....";
+        assert_eq!(read_str(s), Ok("FZBPBFZF".to_owned()));
+        assert_eq!(
+            read_str(&s.replace('#', "█").replace('.', " ")),
+            Ok("FZBPBFZF".to_owned())
+        );
+        let s = "\
+#...#.###..####
+#...#..#...#...
+.#.#...#...###.
+..#....#...#...
+..#....#...#...
+..#...###..####";
+        assert_eq!(read_str(s), Ok("YIE".to_owned()));
+    }
+
+    #[test]
+    fn test_read_grid_with_margin() {
+        let (_, glyph) = FONT_10.iter().find(|&&(letter, _)| letter == 'X').unwrap();
+        let mut grid = Grid(Array2::default((12, 9)));
+        for (i, line) in glyph.lines().enumerate() {
+            for (j, c) in line.chars().enumerate() {
+                grid[[i + 1, j + 2]] = c == '#';
+            }
+        }
+        assert_eq!(read_grid(&grid), Ok("X".to_owned()));
+    }
+
+    #[test]
+    fn test_read_unknown_letter() {
+        let s = ".##.\n#..#\n#..#\n####\n#..#\n#..#\n\n";
+        assert_eq!(read_str(s), Ok("A".to_owned()));
+        let error = read_str("###\n#.#\n###\n#.#\n#.#\n###").unwrap_err();
+        assert_eq!(
+            error,
+            OcrError::UnknownLetter {
+                index: 0,
+                pixels: "###\n#.#\n###\n#.#\n#.#\n###".to_owned()
+            }
+        );
+        assert_eq!(read_str("#\n#"), Err(OcrError::UnknownFont { height: 2 }));
+    }
+}
@@ -0,0 +1,323 @@
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Defines a struct with a `FromStr` impl that reads it from text laid out
+/// like a pattern string, where each `{field}` placeholder is replaced by the
+/// value of that field. Use `{{` and `}}` for literal braces. The
+/// `#[pattern]` attribute must come first.
+///
+/// ```text
+/// pattern_struct! {
+///     #[pattern("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}")]
+///     #[derive(Debug)]
+///     struct Reading {
+///         sx: i32,
+///         sy: i32,
+///         bx: i32,
+///         by: i32,
+///     }
+/// }
+/// ```
+///
+/// Each field's type must implement `PatternField`, which says what its
+/// values look like, so that the pattern can be turned into a regex.
+#[macro_export]
+macro_rules! pattern_struct {
+    (
+        #[pattern($pattern:literal)]
+        $(#[$attr:meta])*
+        $visibility:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_visibility:vis $field:ident: $T:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $visibility struct $name {
+            $($(#[$field_attr])* $field_visibility $field: $T,)*
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = $crate::util::pattern::PatternError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                static PATTERN: once_cell::sync::OnceCell<$crate::util::pattern::Pattern> =
+                    once_cell::sync::OnceCell::new();
+                let pattern = PATTERN.get_or_init(|| {
+                    $crate::util::pattern::Pattern::new(
+                        $pattern,
+                        &[$((
+                            stringify!($field),
+                            <$T as $crate::util::pattern::PatternField>::regex(),
+                        )),*],
+                    )
+                });
+                let _caps = pattern.captures(s)?;
+                Ok(Self {
+                    $($field: $crate::util::pattern::parse_field(&_caps, stringify!($field))?,)*
+                })
+            }
+        }
+    };
+}
+
+/// A type that can fill a placeholder in a `pattern_struct!` pattern.
+pub trait PatternField: FromStr {
+    /// A regex that matches the text of any value, without anchors or capture
+    /// groups.
+    fn regex() -> Cow<'static, str>;
+}
+
+macro_rules! impl_pattern_field {
+    ($regex:literal: $($T:ty),*) => {
+        $(
+            impl PatternField for $T {
+                fn regex() -> Cow<'static, str> {
+                    Cow::Borrowed($regex)
+                }
+            }
+        )*
+    };
+}
+
+impl_pattern_field!(r"[-+]?\d+": i8, i16, i32, i64, i128, isize);
+impl_pattern_field!(r"\+?\d+": u8, u16, u32, u64, u128, usize);
+impl_pattern_field!(r"[-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?": f32, f64);
+impl_pattern_field!("true|false": bool);
+impl_pattern_field!(".": char);
+// Lazy, so that a string stops at the first text that can follow it.
+impl_pattern_field!(".+?": String);
+
+/// A regex matching any of `values`, for types like those made by
+/// `string_enum!` that have a fixed set of values.
+pub fn alternatives(values: &[&str]) -> Cow<'static, str> {
+    let values: Vec<_> = values.iter().map(|value| regex::escape(value)).collect();
+    Cow::Owned(values.join("|"))
+}
+
+/// A pattern compiled to a regex, for `pattern_struct!`.
+#[derive(Debug)]
+pub struct Pattern {
+    regex: Regex,
+    pieces: Vec<Piece>,
+}
+
+#[derive(Debug)]
+enum Piece {
+    Text(String),
+    Placeholder {
+        name: &'static str,
+        regex: Cow<'static, str>,
+    },
+}
+
+impl Piece {
+    fn regex(&self) -> String {
+        match self {
+            Piece::Text(text) => regex::escape(text),
+            Piece::Placeholder { name, regex } => format!("(?P<{}>{})", name, regex),
+        }
+    }
+}
+
+impl Pattern {
+    /// Compiles `pattern`, where `fields` are the name of each field and the
+    /// regex for its type. Panics unless every field appears exactly once, since
+    /// that is a mistake in the code rather than the input.
+    pub fn new(pattern: &str, fields: &[(&'static str, Cow<'static, str>)]) -> Self {
+        let mut pieces = vec![];
+        let mut text = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let (name, rest) = chars
+                        .as_str()
+                        .split_once('}')
+                        .unwrap_or_else(|| panic!("Unclosed placeholder in {:?}.", pattern));
+                    let (name, regex) = fields
+                        .iter()
+                        .find(|(field, _)| *field == name)
+                        .unwrap_or_else(|| panic!("{{{}}} in {:?} is not a field.", name, pattern))
+                        .clone();
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(text.split_off(0)));
+                    }
+                    pieces.push(Piece::Placeholder { name, regex });
+                    chars = rest.chars();
+                }
+                '}' => panic!("Unmatched }} in {:?}.", pattern),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        for (field, _) in fields {
+            let count = pieces
+                .iter()
+                .filter(|piece| matches!(piece, Piece::Placeholder { name, .. } if name == field))
+                .count();
+            assert_eq!(count, 1, "{{{}}} must appear once in {:?}.", field, pattern);
+        }
+        let regex: String = pieces.iter().map(Piece::regex).collect();
+        Self {
+            regex: Regex::new(&format!("^{}$", regex)).unwrap(),
+            pieces,
+        }
+    }
+
+    /// Matches `s` against the whole pattern, or finds the first part of the
+    /// pattern that it doesn't match.
+    pub fn captures<'s>(&self, s: &'s str) -> Result<Captures<'s>, PatternError> {
+        if let Some(caps) = self.regex.captures(s) {
+            return Ok(caps);
+        }
+        let mut prefix = String::from("^");
+        let mut end = 0;
+        for piece in &self.pieces {
+            prefix += &piece.regex();
+            match Regex::new(&prefix).unwrap().find(s) {
+                Some(m) => end = m.end(),
+                None => {
+                    let found = s[end..].to_owned();
+                    return Err(match piece {
+                        Piece::Text(text) => PatternError::Mismatch {
+                            expected: text.clone(),
+                            found,
+                        },
+                        Piece::Placeholder { name, .. } => PatternError::NoMatch {
+                            placeholder: name,
+                            found,
+                        },
+                    });
+                }
+            }
+        }
+        Err(PatternError::Mismatch {
+            expected: String::new(),
+            found: s[end..].to_owned(),
+        })
+    }
+}
+
+/// Parses the text matched by `placeholder`, for `pattern_struct!`.
+pub fn parse_field<T>(caps: &Captures, placeholder: &'static str) -> Result<T, PatternError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+{
+    let text = caps.name(placeholder).unwrap().as_str();
+    text.parse().map_err(|error: T::Err| PatternError::Parse {
+        placeholder,
+        text: text.to_owned(),
+        reason: error.into(),
+    })
+}
+
+/// Why text could not be read with a `pattern_struct!` pattern.
+#[derive(Debug)]
+pub enum PatternError {
+    /// Text from the pattern was missing. `expected` is empty if the text went
+    /// on after the end of the pattern.
+    Mismatch { expected: String, found: String },
+    /// A placeholder did not match a value of its field's type.
+    NoMatch {
+        placeholder: &'static str,
+        found: String,
+    },
+    /// A placeholder matched text that its field's type could not parse.
+    Parse {
+        placeholder: &'static str,
+        text: String,
+        reason: Box<dyn error::Error>,
+    },
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Mismatch { expected, found } if expected.is_empty() => {
+                write!(f, "Unexpected text at the end: {:?}", found)
+            }
+            PatternError::Mismatch { expected, found } => {
+                write!(f, "Expected {:?}, found {:?}", expected, found)
+            }
+            PatternError::NoMatch { placeholder, found } => {
+                write!(f, "{{{}}} did not match {:?}", placeholder, found)
+            }
+            PatternError::Parse {
+                placeholder,
+                text,
+                reason,
+            } => write!(f, "{{{}}} ({:?}): {}", placeholder, text, reason),
+        }
+    }
+}
+
+impl error::Error for PatternError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::string_enum;
+
+    string_enum!(Direction {
+        Left = "L",
+        Right = "R",
+    });
+
+    pattern_struct! {
+        #[pattern("Sensor at x={x}, y={y}: {{{name}}} turns {direction}")]
+        #[derive(Debug, PartialEq)]
+        struct Sensor {
+            x: i32,
+            y: u8,
+            name: String,
+            direction: Direction,
+        }
+    }
+
+    #[test]
+    fn test_pattern_struct() {
+        let sensor: Sensor = "Sensor at x=-2, y=18: {A b} turns R".parse().unwrap();
+        assert_eq!(
+            sensor,
+            Sensor {
+                x: -2,
+                y: 18,
+                name: "A b".to_owned(),
+                direction: Direction::Right,
+            }
+        );
+    }
+
+    #[test]
+    fn test_pattern_struct_errors() {
+        let error = |s: &str| s.parse::<Sensor>().unwrap_err().to_string();
+        assert_eq!(
+            error("Sensor at x=a, y=18: {A} turns L"),
+            r#"{x} did not match "a, y=18: {A} turns L""#
+        );
+        assert_eq!(
+            error("Sensor at x=2; y=18: {A} turns L"),
+            r#"Expected ", y=", found "; y=18: {A} turns L""#
+        );
+        assert_eq!(
+            error("Sensor at x=2, y=300: {A} turns L"),
+            r#"{y} ("300"): number too large to fit in target type"#
+        );
+        assert_eq!(
+            error("Sensor at x=2, y=18: {A} turns L twice"),
+            r#"Unexpected text at the end: " twice""#
+        );
+    }
+}
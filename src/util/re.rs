@@ -1,6 +1,7 @@
+use arrayvec::ArrayString;
 use regex::{Captures, Regex};
-use std::convert::Infallible;
 use std::error;
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
 /// Converts a string literal into a Regex, caching the value in a static variable for reuse.
@@ -18,33 +19,200 @@ pub fn parse_with_regex<'a, T: MatchTuple<'a>>(
     re: &Regex,
     s: &'a str,
 ) -> Result<T, Box<dyn error::Error>> {
-    let caps = re.captures(s).ok_or("Regex did not match string.")?;
-    if caps.len() != T::len() + 1 {
-        Err(format!(
-            "Expected {} (non-global) capture groups, found {}.",
-            T::len(),
-            caps.len() - 1,
-        ))?
-    }
+    T::check_regex(re)?;
+    let caps = re
+        .captures(s)
+        .ok_or_else(|| format!("Regex did not match string: {}", re.as_str()))?;
     T::parse_captures(&caps)
 }
 
+/// Parses a single capture group, with an error that names the group and the
+/// text it captured.
+fn parse_capture<'a, T: FromCapture<'a>>(
+    caps: &Captures<'a>,
+    group: usize,
+) -> Result<T, CaptureError> {
+    let text = caps.get(group).map(|m| m.as_str());
+    T::from_capture(text).map_err(|reason| CaptureError {
+        group: Group::Index(group),
+        text: text.map(str::to_owned),
+        reason,
+    })
+}
+
+/// Like `parse_capture`, for a named group.
+#[doc(hidden)]
+pub fn parse_named_capture<'a, T: FromCapture<'a>>(
+    caps: &Captures<'a>,
+    name: &'static str,
+) -> Result<T, CaptureError> {
+    let text = caps.name(name).map(|m| m.as_str());
+    T::from_capture(text).map_err(|reason| CaptureError {
+        group: Group::Name(name),
+        text: text.map(str::to_owned),
+        reason,
+    })
+}
+
+/// A capture group, by its 1-based index or by name.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Group {
+    Index(usize),
+    Name(&'static str),
+}
+
+impl Display for Group {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Group::Index(index) => write!(f, "{}", index),
+            Group::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// A capture group that did not participate in a match or could not be
+/// parsed.
+#[derive(Debug)]
+pub struct CaptureError {
+    pub group: Group,
+    /// The captured text, or `None` if the group did not participate.
+    pub text: Option<String>,
+    pub reason: Box<dyn error::Error>,
+}
+
+impl Display for CaptureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.text {
+            Some(text) => write!(
+                f,
+                "Capture group {} ({:?}): {}",
+                self.group, text, self.reason
+            ),
+            None => write!(f, "Capture group {}: {}", self.group, self.reason),
+        }
+    }
+}
+
+impl error::Error for CaptureError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.reason)
+    }
+}
+
 pub trait MatchTuple<'a>: Sized {
     fn len() -> usize;
     fn parse_captures(caps: &Captures<'a>) -> Result<Self, Box<dyn error::Error>>;
+
+    /// Checks that `re` has the capture groups that `parse_captures` reads.
+    fn check_regex(re: &Regex) -> Result<(), Box<dyn error::Error>> {
+        let found = re.captures_len() - 1;
+        if found != Self::len() {
+            Err(format!(
+                "Expected {} (non-global) capture groups, found {}.",
+                Self::len(),
+                found,
+            ))?
+        }
+        Ok(())
+    }
+}
+
+/// A value that can be read from a single capture group.
+pub trait FromCapture<'a>: Sized {
+    /// Reads the value from the text of the group, which is `None` if the group
+    /// did not participate in the match.
+    fn from_capture(text: Option<&'a str>) -> Result<Self, Box<dyn error::Error>>;
 }
 
-pub trait FromStr2<'a>: Sized {
-    type Err;
+/// Implements `FromCapture` for types that implement `FromStr`. A blanket impl
+/// would rule out the impls for `Option` and `Vec`. Other `FromStr` types can
+/// be read through `Parsed` instead.
+#[macro_export]
+macro_rules! impl_from_capture {
+    ($($T:ty),* $(,)?) => {
+        $(
+            impl<'a> $crate::util::re::FromCapture<'a> for $T {
+                fn from_capture(
+                    text: Option<&'a str>,
+                ) -> Result<Self, Box<dyn std::error::Error>> {
+                    Ok(text.ok_or("Missing capture")?.parse::<$T>()?)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_capture!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_from_capture!(f32, f64, bool, char, String);
+
+impl<'a, const CAP: usize> FromCapture<'a> for ArrayString<CAP> {
+    fn from_capture(text: Option<&'a str>) -> Result<Self, Box<dyn error::Error>> {
+        let text = text.ok_or("Missing capture")?;
+        Ok(ArrayString::from(text).map_err(|error| error.simplify())?)
+    }
+}
+
+/// An optional group, which is `None` if it did not participate in the match.
+impl<'a, T: FromCapture<'a>> FromCapture<'a> for Option<T> {
+    fn from_capture(text: Option<&'a str>) -> Result<Self, Box<dyn error::Error>> {
+        text.map(|text| T::from_capture(Some(text))).transpose()
+    }
+}
+
+/// A list, split on commas and whitespace, such as `a, b, c`. An empty capture
+/// or a group that did not participate is an empty list.
+impl<'a, T: FromCapture<'a>> FromCapture<'a> for Vec<T> {
+    fn from_capture(text: Option<&'a str>) -> Result<Self, Box<dyn error::Error>> {
+        parse_items(
+            regex!(r"[,\s]+")
+                .split(text.unwrap_or(""))
+                .filter(|item| !item.is_empty()),
+        )
+    }
+}
+
+/// A list split on `SEP`, such as `List<u32, ';'>` for `1; 2; 3`. Whitespace
+/// around each item is ignored. An empty capture or a group that did not
+/// participate is an empty list.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct List<T, const SEP: char>(pub Vec<T>);
+
+impl<'a, T: FromCapture<'a>, const SEP: char> FromCapture<'a> for List<T, SEP> {
+    fn from_capture(text: Option<&'a str>) -> Result<Self, Box<dyn error::Error>> {
+        let text = text.unwrap_or("").trim();
+        if text.is_empty() {
+            return Ok(Self(vec![]));
+        }
+        parse_items(text.split(SEP).map(str::trim)).map(Self)
+    }
+}
 
-    fn from_str(s: &'a str) -> Result<Self, Self::Err>;
+/// Parses the items of a list, with an error that names the item.
+fn parse_items<'a, T: FromCapture<'a>>(
+    items: impl Iterator<Item = &'a str>,
+) -> Result<Vec<T>, Box<dyn error::Error>> {
+    items
+        .enumerate()
+        .map(|(i, item)| {
+            T::from_capture(Some(item))
+                .map_err(|error| format!("Item {} ({:?}): {}", i + 1, item, error).into())
+        })
+        .collect()
 }
 
-impl<'a, T: FromStr> FromStr2<'a> for T {
-    type Err = T::Err;
+/// A value of any `FromStr` type, for types that don't implement
+/// `FromCapture` themselves.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Parsed<T>(pub T);
 
-    fn from_str(s: &'a str) -> Result<Self, Self::Err> {
-        s.parse()
+impl<'a, T> FromCapture<'a> for Parsed<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<Box<dyn error::Error>>,
+{
+    fn from_capture(text: Option<&'a str>) -> Result<Self, Box<dyn error::Error>> {
+        let text = text.ok_or("Missing capture")?;
+        text.parse().map(Self).map_err(Into::into)
     }
 }
 
@@ -57,11 +225,9 @@ impl<'a> Str<'a> {
     }
 }
 
-impl<'a> FromStr2<'a> for Str<'a> {
-    type Err = Infallible;
-
-    fn from_str(s: &'a str) -> Result<Self, Self::Err> {
-        Ok(Self(s))
+impl<'a> FromCapture<'a> for Str<'a> {
+    fn from_capture(text: Option<&'a str>) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Self(text.ok_or("Missing capture")?))
     }
 }
 
@@ -70,8 +236,7 @@ macro_rules! impl_match_tuple {
         impl <'a, $($T, )*> MatchTuple<'a> for ($($T, )*)
         where
             $(
-                $T: FromStr2<'a>,
-                <$T as FromStr2<'a>>::Err: std::error::Error + 'static,
+                $T: FromCapture<'a>,
             )*
         {
             fn len() -> usize {
@@ -99,7 +264,7 @@ macro_rules! parse_to_tuple {
         as_expr!(($($body)*))
     };
     (@accum [$caps:ident][$head:ident $($tail:tt)*][$($n:tt)*] -> [$($body:tt)*]) => {
-        parse_to_tuple!(@accum [$caps][$($tail)*][$($n)* + 1] -> [$($body)* FromStr2::from_str(&$caps.get($($n)*).ok_or("Missing capture")?.as_str())?,])
+        parse_to_tuple!(@accum [$caps][$($tail)*][$($n)* + 1] -> [$($body)* parse_capture(&$caps, $($n)*)?,])
     };
     (@as_expr $e:expr) => {
         $e
@@ -123,10 +288,63 @@ impl_match_tuple!(A, B, C, D);
 impl_match_tuple!(A, B, C, D, E);
 impl_match_tuple!(A, B, C, D, E, F);
 impl_match_tuple!(A, B, C, D, E, F, G);
+impl_match_tuple!(A, B, C, D, E, F, G, H);
+impl_match_tuple!(A, B, C, D, E, F, G, H, I);
+impl_match_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_match_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_match_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_match_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_match_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_match_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_match_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+/// Defines a struct that `parse_with_regex` fills from named capture groups,
+/// such as `(?P<flow_rate>\d+)`, one per field. Each field's type implements
+/// `FromCapture`, as in a tuple.
+#[macro_export]
+macro_rules! captures_struct {
+    (
+        $(#[$attr:meta])*
+        $visibility:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_visibility:vis $field:ident: $T:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $visibility struct $name {
+            $($(#[$field_attr])* $field_visibility $field: $T,)*
+        }
+
+        impl<'a> $crate::util::re::MatchTuple<'a> for $name {
+            fn len() -> usize {
+                [$(stringify!($field)),*].len()
+            }
+
+            fn parse_captures(
+                _caps: &regex::Captures<'a>,
+            ) -> Result<Self, Box<dyn std::error::Error>> {
+                Ok(Self {
+                    $($field: $crate::util::re::parse_named_capture(_caps, stringify!($field))?,)*
+                })
+            }
+
+            fn check_regex(re: &regex::Regex) -> Result<(), Box<dyn std::error::Error>> {
+                let names: Vec<_> = re.capture_names().flatten().collect();
+                for field in [$(stringify!($field)),*] {
+                    if !names.contains(&field) {
+                        Err(format!("Missing capture group (?P<{}>...).", field))?
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::net::Ipv4Addr;
+    use std::num::NonZeroU32;
 
     #[test]
     fn test_parse_match() {
@@ -137,4 +355,109 @@ mod tests {
         assert_eq!(last_name, "Luthor");
         assert_eq!(count, 40);
     }
+
+    #[test]
+    fn test_parse_match_error_names_group() {
+        let re = regex!(r"^(.+) stole (.+) cakes.$");
+        let error = parse_with_regex::<(String, usize)>(re, "Lex stole forty cakes.").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Capture group 2 ("forty"): invalid digit found in string"#
+        );
+    }
+
+    #[test]
+    fn test_parse_match_optional() {
+        let re = regex!(r"^(\w+)(?: x(\d+))?$");
+        let (item, count): (String, Option<u32>) = parse_with_regex(re, "apple x3").unwrap();
+        assert_eq!((item.as_str(), count), ("apple", Some(3)));
+        let (item, count): (String, Option<u32>) = parse_with_regex(re, "pear").unwrap();
+        assert_eq!((item.as_str(), count), ("pear", None));
+        let error = parse_with_regex::<(String, u32)>(re, "pear").unwrap_err();
+        assert_eq!(error.to_string(), "Capture group 2: Missing capture");
+    }
+
+    #[test]
+    fn test_parse_match_list() {
+        let re = regex!(r"^Starting items: (.*)$");
+        let (items,): (Vec<u64>,) = parse_with_regex(re, "Starting items: 79, 98").unwrap();
+        assert_eq!(items, vec![79, 98]);
+        let (items,): (Vec<u64>,) = parse_with_regex(re, "Starting items: ").unwrap();
+        assert!(items.is_empty());
+        let error = parse_with_regex::<(Vec<u64>,)>(re, "Starting items: 79, x").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Capture group 1 ("79, x"): Item 2 ("x"): invalid digit found in string"#
+        );
+    }
+
+    #[test]
+    fn test_parse_match_list_with_separator() {
+        let re = regex!(r"^(.*) -> (.*)$");
+        let (List(numbers), List(names)): (List<u32, ';'>, List<String, '|'>) =
+            parse_with_regex(re, "1; 2;3 -> a b | c").unwrap();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(names, vec!["a b".to_owned(), "c".to_owned()]);
+        let (List(numbers), _): (List<u32, ';'>, Str) = parse_with_regex(re, " -> ").unwrap();
+        assert!(numbers.is_empty());
+        let error = parse_with_regex::<(List<u32, ';'>, Str)>(re, "1;;2 -> ").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Capture group 1 ("1;;2"): Item 2 (""): cannot parse integer from empty string"#
+        );
+    }
+
+    #[test]
+    fn test_parse_match_from_str() {
+        let re = regex!(r"^(\S+) has (\d+)$");
+        let (Parsed(address), Parsed(count)): (Parsed<Ipv4Addr>, Parsed<NonZeroU32>) =
+            parse_with_regex(re, "10.0.0.1 has 3").unwrap();
+        assert_eq!(address, Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(count.get(), 3);
+        let error =
+            parse_with_regex::<(Str, Parsed<NonZeroU32>)>(re, "10.0.0.1 has 0").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Capture group 2 ("0"): number would be zero for non-zero type"#
+        );
+    }
+
+    #[test]
+    fn test_parse_match_large_tuple() {
+        let re =
+            regex!(r"^(\d+) (\d+) (\d+) (\d+) (\d+) (\d+) (\d+) (\d+) (\d+) (\d+) (\d+) (\d+)$");
+        let values: (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8) =
+            parse_with_regex(re, "1 2 3 4 5 6 7 8 9 10 11 12").unwrap();
+        assert_eq!(values.0 + values.11, 13);
+    }
+
+    captures_struct! {
+        #[derive(Debug, PartialEq)]
+        struct Theft {
+            thief: String,
+            victim: Option<String>,
+            count: usize,
+        }
+    }
+
+    #[test]
+    fn test_parse_match_named() {
+        let re = regex!(r"^(?P<thief>\w+) stole (?P<count>\w+) cakes(?: from (?P<victim>\w+))?.$");
+        let theft: Theft = parse_with_regex(re, "Lex stole 40 cakes from Clark.").unwrap();
+        assert_eq!(
+            theft,
+            Theft {
+                thief: "Lex".to_owned(),
+                victim: Some("Clark".to_owned()),
+                count: 40,
+            }
+        );
+        let error = parse_with_regex::<Theft>(re, "Lex stole forty cakes.").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Capture group count ("forty"): invalid digit found in string"#
+        );
+        let error = parse_with_regex::<Theft>(regex!(r"^(?P<thief>\w+)$"), "Lex").unwrap_err();
+        assert_eq!(error.to_string(), "Missing capture group (?P<victim>...).");
+    }
 }
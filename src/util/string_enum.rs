@@ -37,6 +37,14 @@ macro_rules! string_enum {
                 }
             }
         }
+
+        $crate::impl_from_capture!($name);
+
+        impl $crate::util::pattern::PatternField for $name {
+            fn regex() -> std::borrow::Cow<'static, str> {
+                $crate::util::pattern::alternatives(&[$($s),*])
+            }
+        }
     };
 }
 
@@ -0,0 +1,66 @@
+//! Runs every day's solvers against its test inputs. Filter by day or part
+//! with e.g. `cargo test -- day17` or `cargo test -- day17::part2`.
+
+use advent_of_code_2022::harness::day::Day;
+use advent_of_code_2022::harness::report::Status;
+use advent_of_code_2022::harness::test_input::{self, TestInput};
+use advent_of_code_2022::harness::{DayArgs, DaySolver};
+use advent_of_code_2022::with_days;
+use std::path::Path;
+
+macro_rules! example_tests {
+    ($($module:ident::$day:ident $(($part1:ident, $part2:ident))?),* $(,)?) => {
+        $(
+            mod $module {
+                use advent_of_code_2022::days::$module;
+                use advent_of_code_2022::registered_solver;
+
+                #[test]
+                fn part1() {
+                    super::check_examples(registered_solver!($module::$day $(($part1, $part2))?), 1);
+                }
+
+                #[test]
+                fn part2() {
+                    super::check_examples(registered_solver!($module::$day $(($part1, $part2))?), 2);
+                }
+            }
+        )*
+    };
+}
+
+with_days!(example_tests);
+
+fn check_examples<D: Day>(solver: &D, part: usize) {
+    let day = D::NUMBER;
+    let files = test_input::read_test_files(Path::new("input"), day).unwrap();
+    let test_inputs: Vec<_> = files
+        .iter()
+        .map(|file| TestInput::parse(file).unwrap())
+        .chain(D::TEST_INPUTS.iter().copied())
+        .collect();
+    let report = solver
+        .solve(DayArgs {
+            day,
+            parts: &[part],
+            input: None,
+            test_inputs: &test_inputs,
+            recorded_answers: [None, None],
+        })
+        .remove(0);
+    if let Some(error) = &report.error {
+        panic!("Day {} part {}: {}", day, part, error.describe());
+    }
+    if report.status() == Status::Unimplemented {
+        println!("Day {} part {} is not implemented.", day, part);
+    } else if report.tests.is_empty() {
+        println!("Day {} part {} has no expected test output.", day, part);
+    }
+    for test in &report.tests {
+        assert!(
+            test.passed,
+            "Day {} part {} test {}: got {}, expected {}",
+            day, part, test.name, test.output, test.expected
+        );
+    }
+}